@@ -0,0 +1,201 @@
+use std::{collections::HashMap, fmt, io::ErrorKind, path::Path};
+
+pub const ANSWERS_FILE: &str = "answers.toml";
+
+/**
+ * Accepted answers for the inputs of a day, read from an `answers.toml` file
+ * which lives next to the inputs:
+ *
+ * ```toml
+ * # answers for input.txt, or the first embedded input
+ * part1 = 1722302
+ * part2 = "61,50"
+ *
+ * # answers for the input labelled Jared, or jared.txt
+ * [Jared]
+ * part1 = 5162
+ * ```
+ *
+ * Only the small subset of TOML needed for this is supported: comments,
+ * table headers, and keys with integer or basic string values.
+ */
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Answers {
+    tables: HashMap<String, HashMap<String, String>>,
+}
+
+const TOP_LEVEL: &str = "";
+
+impl Answers {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut answers = Self::default();
+        let mut table = TOP_LEVEL.to_owned();
+        for (line_number, line) in text.lines().enumerate() {
+            let error = |message: &str| format!("line {}: {message}: {line}", line_number + 1);
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                table = name.trim().to_owned();
+                continue;
+            }
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| error("expected key = value"))?;
+            let value = parse_value(value.trim()).ok_or_else(|| error("invalid value"))?;
+            answers
+                .tables
+                .entry(table.clone())
+                .or_default()
+                .insert(key.trim().to_owned(), value);
+        }
+        Ok(answers)
+    }
+
+    /**
+     * Reads the answers file in the same directory as an input file.
+     * Missing files have no answers, but one which can't be read is an error.
+     */
+    pub fn read_beside(input_path: &str) -> Result<Self, String> {
+        let path = Path::new(input_path).with_file_name(ANSWERS_FILE);
+        match std::fs::read_to_string(&path) {
+            Ok(text) => Self::parse(&text).map_err(|e| format!("{}: {e}", path.display())),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(format!("{}: {e}", path.display())),
+        }
+    }
+
+    /**
+     * Gets the answer for `part` from the table named `table`, or from the
+     * top level keys when `table` is `None`.
+     */
+    pub fn get(&self, table: Option<&str>, part: &str) -> Option<&str> {
+        self.tables
+            .get(table.unwrap_or(TOP_LEVEL))
+            .and_then(|t| t.get(part))
+            .map(String::as_str)
+    }
+}
+
+fn parse_value(value: &str) -> Option<String> {
+    if let Some(quoted) = value.strip_prefix('"') {
+        let mut result = String::new();
+        let mut chars = quoted.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '"' => return chars.as_str().trim().is_empty().then_some(result),
+                '\\' => result.push(match chars.next()? {
+                    'n' => '\n',
+                    't' => '\t',
+                    c @ ('"' | '\\') => c,
+                    _ => return None,
+                }),
+                c => result.push(c),
+            }
+        }
+        None
+    } else {
+        value.parse::<i128>().ok().map(|n| n.to_string())
+    }
+}
+
+/**
+ * How an answer compares to the accepted answer for its input.
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub enum Check {
+    Pass,
+    Fail { expected: String },
+    Unknown,
+}
+
+impl Check {
+    pub fn compare(answer: &str, expected: Option<&str>) -> Self {
+        match expected {
+            Some(expected) if expected == answer => Self::Pass,
+            Some(expected) => Self::Fail {
+                expected: expected.to_owned(),
+            },
+            None => Self::Unknown,
+        }
+    }
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Pass => f.write_str("PASS"),
+            Self::Fail { expected } => write!(f, "FAIL (expected {expected})"),
+            Self::Unknown => f.write_str("UNKNOWN"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let answers = Answers::parse(
+            r#"
+# comment
+part1 = 123
+part2 = "a,b"
+
+[Jared]
+part1 = -4
+part2 = "say \"hi\""
+"#,
+        )
+        .unwrap();
+        assert_eq!(answers.get(None, "part1"), Some("123"));
+        assert_eq!(answers.get(None, "part2"), Some("a,b"));
+        assert_eq!(answers.get(Some("Jared"), "part1"), Some("-4"));
+        assert_eq!(answers.get(Some("Jared"), "part2"), Some("say \"hi\""));
+        assert_eq!(answers.get(Some("Fred"), "part1"), None);
+        assert_eq!(answers.get(None, "part3"), None);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!(Answers::parse("part1").is_err());
+        assert!(Answers::parse("part1 = abc").is_err());
+        assert!(Answers::parse("part1 = \"abc").is_err());
+        assert!(Answers::parse("part1 = \"abc\" x").is_err());
+    }
+
+    #[test]
+    fn read_beside() {
+        let dir = std::env::temp_dir().join(format!("aoc-answers-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let input = dir.join("input.txt");
+        let input = input.to_str().unwrap();
+        assert_eq!(
+            Answers::read_beside(input).unwrap().get(None, "part1"),
+            None
+        );
+        std::fs::write(dir.join(ANSWERS_FILE), "part1 = 5").unwrap();
+        assert_eq!(
+            Answers::read_beside(input).unwrap().get(None, "part1"),
+            Some("5")
+        );
+        std::fs::write(dir.join(ANSWERS_FILE), b"part1 = \"\xff\"").unwrap();
+        let error = Answers::read_beside(input).unwrap_err();
+        assert!(error.contains(ANSWERS_FILE), "{error}");
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn compare() {
+        assert_eq!(Check::compare("1", Some("1")), Check::Pass);
+        assert_eq!(
+            Check::compare("1", Some("2")),
+            Check::Fail {
+                expected: "2".to_owned()
+            }
+        );
+        assert_eq!(Check::compare("1", None), Check::Unknown);
+    }
+}
@@ -0,0 +1,124 @@
+use std::{
+    fmt,
+    time::{Duration, Instant},
+};
+
+pub const DEFAULT_BENCH_RUNS: usize = 10;
+
+/**
+ * Runs `f` once and returns its result along with the wall time it took.
+ */
+pub fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+#[derive(Debug, PartialEq)]
+pub struct BenchStats {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub max: Duration,
+}
+
+impl BenchStats {
+    pub fn from_times(mut times: Vec<Duration>) -> Option<Self> {
+        times.sort();
+        let runs = times.len();
+        let min = *times.first()?;
+        let max = *times.last()?;
+        let median = if runs.is_multiple_of(2) {
+            (times[runs / 2 - 1] + times[runs / 2]) / 2
+        } else {
+            times[runs / 2]
+        };
+        let mean = times.iter().sum::<Duration>() / runs as u32;
+        Some(Self {
+            runs,
+            min,
+            median,
+            mean,
+            max,
+        })
+    }
+}
+
+impl fmt::Display for BenchStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{runs} runs: min {min:.2?}, median {median:.2?}, mean {mean:.2?}, max {max:.2?}",
+            runs = self.runs,
+            min = self.min,
+            median = self.median,
+            mean = self.mean,
+            max = self.max,
+        )
+    }
+}
+
+/**
+ * Runs `f` `runs` times, returning the result of the last run and the timing
+ * statistics of all of them.
+ */
+pub fn bench<T>(runs: usize, mut f: impl FnMut() -> T) -> (T, BenchStats) {
+    let (mut result, first) = timed(&mut f);
+    let mut times = vec![first];
+    for _ in 1..runs {
+        let (next, elapsed) = timed(&mut f);
+        result = next;
+        times.push(elapsed);
+    }
+    (
+        result,
+        BenchStats::from_times(times).expect("at least one run"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn millis(ms: &[u64]) -> Vec<Duration> {
+        ms.iter().map(|&ms| Duration::from_millis(ms)).collect()
+    }
+
+    #[test]
+    fn stats_odd() {
+        assert_eq!(
+            BenchStats::from_times(millis(&[5, 1, 3])),
+            Some(BenchStats {
+                runs: 3,
+                min: Duration::from_millis(1),
+                median: Duration::from_millis(3),
+                mean: Duration::from_millis(3),
+                max: Duration::from_millis(5),
+            })
+        );
+    }
+
+    #[test]
+    fn stats_even() {
+        let stats = BenchStats::from_times(millis(&[4, 1, 2, 9])).unwrap();
+        assert_eq!(stats.median, Duration::from_millis(3));
+        assert_eq!(stats.mean, Duration::from_millis(4));
+    }
+
+    #[test]
+    fn stats_empty() {
+        assert_eq!(BenchStats::from_times(Vec::new()), None);
+    }
+
+    #[test]
+    fn bench_runs() {
+        let mut count = 0;
+        let (result, stats) = bench(4, || {
+            count += 1;
+            count
+        });
+        assert_eq!(result, 4);
+        assert_eq!(stats.runs, 4);
+    }
+}
@@ -0,0 +1,365 @@
+use advent_of_code::{
+    format::Format,
+    runner::{exit_code, list_parts, print_summary, read_input, Options, OPTIONS_HELP},
+    readme::{validate_day, Readme},
+    scaffold::{register, NewDay},
+    Day, Runner,
+};
+use itertools::Itertools;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::{exit, ExitCode},
+};
+
+#[allow(dead_code)]
+#[path = "../day01/main.rs"]
+mod day01;
+#[allow(dead_code)]
+#[path = "../day02/main.rs"]
+mod day02;
+#[allow(dead_code)]
+#[path = "../day03/main.rs"]
+mod day03;
+#[allow(dead_code)]
+#[path = "../day04/main.rs"]
+mod day04;
+#[allow(dead_code)]
+#[path = "../day05/main.rs"]
+mod day05;
+#[allow(dead_code)]
+#[path = "../day06/main.rs"]
+mod day06;
+#[allow(dead_code)]
+#[path = "../day07/main.rs"]
+mod day07;
+#[allow(dead_code)]
+#[path = "../day08/main.rs"]
+mod day08;
+#[allow(dead_code)]
+#[path = "../day09/main.rs"]
+mod day09;
+#[allow(dead_code)]
+#[path = "../day10/main.rs"]
+mod day10;
+#[allow(dead_code)]
+#[path = "../day11/main.rs"]
+mod day11;
+#[allow(dead_code)]
+#[path = "../day12/main.rs"]
+mod day12;
+#[allow(dead_code)]
+#[path = "../day13/main.rs"]
+mod day13;
+#[allow(dead_code)]
+#[path = "../day14/main.rs"]
+mod day14;
+#[allow(dead_code)]
+#[path = "../day15/main.rs"]
+mod day15;
+#[allow(dead_code)]
+#[path = "../day16/main.rs"]
+mod day16;
+#[allow(dead_code)]
+#[path = "../day17/main.rs"]
+mod day17;
+#[allow(dead_code)]
+#[path = "../day18/main.rs"]
+mod day18;
+#[allow(dead_code)]
+#[path = "../day19/main.rs"]
+mod day19;
+#[allow(dead_code)]
+#[path = "../day20/main.rs"]
+mod day20;
+#[allow(dead_code)]
+#[path = "../day21/main.rs"]
+mod day21;
+#[allow(dead_code)]
+#[path = "../day22/main.rs"]
+mod day22;
+#[allow(dead_code)]
+#[path = "../day23/main.rs"]
+mod day23;
+#[allow(dead_code)]
+#[path = "../day24/main.rs"]
+mod day24;
+#[allow(dead_code)]
+#[path = "../day25/main.rs"]
+mod day25;
+
+fn days() -> Vec<Day> {
+    vec![
+        day01::day(),
+        day02::day(),
+        day03::day(),
+        day04::day(),
+        day05::day(),
+        day06::day(),
+        day07::day(),
+        day08::day(),
+        day09::day(),
+        day10::day(),
+        day11::day(),
+        day12::day(),
+        day13::day(),
+        day14::day(),
+        day15::day(),
+        day16::day(),
+        day17::day(),
+        day18::day(),
+        day19::day(),
+        day20::day(),
+        day21::day(),
+        day22::day(),
+        day23::day(),
+        day24::day(),
+        day25::day(),
+    ]
+}
+
+const USAGE: &str = "usage:
+  aoc run <days> [parts...] [options]   run some days, e.g. `5`, `1,3,5` or `12..18`, where ranges
+                                        include both ends, so `12..18` and `12..=18` include day 18
+  aoc all [parts...] [options]          run every day
+  aoc --help                            show this help with the options of `run` and `all`
+  aoc new <day> [--title TITLE] [--example PATH] [--part1 ANSWER] [--part2 ANSWER]
+                                        create src/bin/dayNN from the template, with an optional
+                                        example (`-` reads stdin) and its expected answers
+  aoc examples <day> [--extract N,...]  list the code blocks in a day's README.md and check the
+                                        examples and tests match it, or write blocks N,... to
+                                        example.txt, example2.txt, ...";
+
+/**
+ * Parses a selection of days, which is a comma separated list of day numbers
+ * and ranges, e.g. `1,3,5..8` selects days 1, 3, 5, 6, 7 and 8. Ranges include
+ * their end, like the days of a calendar, whether written `5..8` or `5..=8`.
+ */
+fn parse_days(spec: &str) -> Option<Vec<u32>> {
+    spec.split(',')
+        .map(|item| {
+            if let Some((start, end)) = item.split_once("..") {
+                let end = end.strip_prefix('=').unwrap_or(end);
+                Some((start.parse().ok()?..=end.parse().ok()?).collect_vec())
+            } else {
+                Some(vec![item.parse().ok()?])
+            }
+        })
+        .collect::<Option<Vec<_>>>()
+        .map(|days| days.concat())
+}
+
+/**
+ * The registered days in a selection, or every day without one. A selection
+ * must name at least one day, and only registered ones.
+ */
+fn select_days<'a>(days: &'a [Day], selected: Option<&[u32]>) -> Result<Vec<&'a Day>, String> {
+    let Some(selected) = selected else {
+        return Ok(days.iter().collect_vec());
+    };
+    if selected.is_empty() {
+        return Err("no days selected".to_owned());
+    }
+    let numbers = days.iter().filter_map(|day| day.number()).collect_vec();
+    if let Some(unknown) = selected.iter().find(|n| !numbers.contains(n)) {
+        return Err(format!("unknown day {unknown}, expected one of {}", numbers.iter().join(", ")));
+    }
+    Ok(days
+        .iter()
+        .filter(|day| day.number().is_some_and(|n| selected.contains(&n)))
+        .collect_vec())
+}
+
+fn usage() -> ! {
+    eprintln!("{USAGE}");
+    exit(2)
+}
+
+fn help() -> ExitCode {
+    println!("{USAGE}\n\n{OPTIONS_HELP}");
+    ExitCode::SUCCESS
+}
+
+/**
+ * Creates a new day from the template and registers it with this binary.
+ */
+fn new_day(mut args: impl Iterator<Item = String>) -> Result<(), String> {
+    let number = args
+        .next()
+        .and_then(|n| n.parse().ok())
+        .ok_or("new requires a day number")?;
+    let mut day = NewDay::create(number);
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("{arg} requires a value"));
+        match arg.as_str() {
+            "--title" => day.title = Some(value()?),
+            "--example" => day.example = Some(read_input(&value()?)?),
+            "--part1" => day.answers[0] = Some(value()?),
+            "--part2" => day.answers[1] = Some(value()?),
+            _ => return Err(format!("unknown option {arg}")),
+        }
+    }
+    let bin_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/bin");
+    let day_dir = day.write(&bin_dir)?;
+    println!("created {}", day_dir.display());
+    let aoc_main = bin_dir.join("aoc/main.rs");
+    let source = fs::read_to_string(&aoc_main).map_err(|e| e.to_string())?;
+    match register(&source, &day.name()).map_err(|e| format!("{}: {e}", aoc_main.display()))? {
+        Some(source) => {
+            fs::write(&aoc_main, source).map_err(|e| e.to_string())?;
+            println!("registered {} in {}", day.name(), aoc_main.display());
+        }
+        None => println!("{} is already registered", day.name()),
+    }
+    Ok(())
+}
+
+fn day_dir(number: u32) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join(format!("src/bin/day{number:02}"))
+}
+
+/**
+ * Lists the code blocks of a day's README.md and checks them against its
+ * example files and tests, or extracts some of them as example files.
+ */
+fn examples(mut args: impl Iterator<Item = String>) -> Result<(), String> {
+    let number: u32 = args
+        .next()
+        .and_then(|n| n.parse().ok())
+        .ok_or("examples requires a day number")?;
+    let day_dir = day_dir(number);
+    let readme = Readme::read(&day_dir)?;
+    match (args.next().as_deref(), args.next()) {
+        (None, _) => {
+            for (index, block) in readme.blocks.iter().enumerate() {
+                println!(
+                    "block {} (part {}, line {}): {}",
+                    index + 1,
+                    block.part,
+                    block.line,
+                    block.text.lines().next().unwrap_or_default()
+                );
+            }
+            let problems = validate_day(&day_dir)?;
+            if problems.is_empty() {
+                println!("examples, tests and answers match README.md");
+            }
+            for problem in problems.iter() {
+                println!("mismatch: {problem}");
+            }
+            Ok(())
+        }
+        (Some("--extract"), Some(blocks)) => {
+            for (index, block) in blocks.split(',').enumerate() {
+                let block = block
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|n| readme.blocks.get(n.checked_sub(1)?))
+                    .ok_or(format!("no block {block} in README.md"))?;
+                let name = match index {
+                    0 => "example.txt".to_owned(),
+                    _ => format!("example{}.txt", index + 1),
+                };
+                let path = day_dir.join(name);
+                if fs::read_to_string(&path).is_ok_and(|text| !text.is_empty()) {
+                    return Err(format!("{} already has an example", path.display()));
+                }
+                fs::write(&path, &block.text).map_err(|e| e.to_string())?;
+                println!("wrote block at line {} to {}", block.line, path.display());
+            }
+            Ok(())
+        }
+        _ => Err(USAGE.to_owned()),
+    }
+}
+
+fn main() -> ExitCode {
+    let mut args = std::env::args().skip(1);
+    let selected = match args.next().as_deref() {
+        Some(command @ ("new" | "examples")) => {
+            let result = match command {
+                "new" => new_day(args),
+                _ => examples(args),
+            };
+            return match result {
+                Ok(()) => ExitCode::SUCCESS,
+                Err(e) => {
+                    eprintln!("{e}");
+                    ExitCode::FAILURE
+                }
+            }
+        }
+        Some("run") => Some(
+            args.next()
+                .as_deref()
+                .and_then(parse_days)
+                .unwrap_or_else(|| usage()),
+        ),
+        Some("all") => None,
+        Some("--help" | "-h" | "help") => return help(),
+        _ => usage(),
+    };
+    let options = Options::parse(args).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        usage()
+    });
+    if options.help {
+        return help();
+    }
+    let days = days();
+    let days = match select_days(&days, selected.as_deref()) {
+        Ok(days) => days,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::from(2);
+        }
+    };
+    if options.list {
+        days.iter().flat_map(|day| list_parts(day)).for_each(|part| println!("{part}"));
+        return ExitCode::SUCCESS;
+    }
+    if let Err(e) = options.check_names(days.iter().copied()) {
+        eprintln!("{e}");
+        return ExitCode::from(2);
+    }
+    let runner = Runner::with_options(options);
+    let records = match runner.run_days(days) {
+        Ok(records) => records,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::from(2);
+        }
+    };
+    if runner.options().format == Format::Text {
+        println!();
+        print_summary(&records);
+    }
+    exit_code(&records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_days() {
+        assert_eq!(parse_days("5"), Some(vec![5]));
+        assert_eq!(parse_days("12..15"), Some(vec![12, 13, 14, 15]));
+        assert_eq!(parse_days("12..=15"), Some(vec![12, 13, 14, 15]));
+        assert_eq!(parse_days("1,3,5..7"), Some(vec![1, 3, 5, 6, 7]));
+        assert_eq!(parse_days("x"), None);
+        assert_eq!(parse_days("1..x"), None);
+    }
+
+    #[test]
+    fn test_select_days() {
+        let days = [Day::create("day03"), Day::create("day05")];
+        let names = |selected: Option<&[u32]>| {
+            select_days(&days, selected).map(|days| days.iter().map(|day| day.name).collect_vec())
+        };
+        assert_eq!(names(None), Ok(vec!["day03", "day05"]));
+        assert_eq!(names(Some(&[5])), Ok(vec!["day05"]));
+        assert_eq!(names(Some(&[])), Err("no days selected".to_owned()));
+        assert_eq!(names(Some(&[3, 30])), Err("unknown day 30, expected one of 3, 5".to_owned()));
+    }
+}
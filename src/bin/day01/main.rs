@@ -1,6 +1,6 @@
-use advent_of_code::{create_runner, named, Named, Runner};
+use advent_of_code::{create_day, named, Day, Named, Runner};
 use num::abs;
-use std::{collections::HashMap, str::Lines};
+use std::{collections::HashMap, process::ExitCode, str::Lines};
 
 fn parse_lists(input: Lines) -> (Vec<i64>, Vec<i64>) {
     input.map(|line| {
@@ -15,17 +15,16 @@ fn parse_lists(input: Lines) -> (Vec<i64>, Vec<i64>) {
     })
 }
 
-fn part1(input: Lines) -> String {
+fn part1(input: Lines) -> i64 {
     let (mut xs, mut ys) = parse_lists(input);
     xs.sort();
     ys.sort();
     xs.iter().zip(ys.iter())
         .map(|(x, y)| abs(x - y))
         .sum::<i64>()
-        .to_string()
 }
 
-fn part2(input: Lines) -> String {
+fn part2(input: Lines) -> i64 {
     let (xs, ys) = parse_lists(input);
     let y_counts: HashMap<i64, i64> = ys.iter().fold(HashMap::new(), |mut counts, y| {
         counts.entry(*y).and_modify(|c| *c += 1).or_insert(1);
@@ -34,25 +33,26 @@ fn part2(input: Lines) -> String {
     xs.iter()
         .map(|x| x * y_counts.get(x).unwrap_or(&0))
         .sum::<i64>()
-        .to_string()
 }
 
-fn main() {
-    let input = include_str!("input.txt");
-    let runner: &Runner = create_runner!();
-    runner.run(named!(part1), input);
-    runner.run(named!(part2), input);
+pub fn day() -> Day {
+    create_day!()
+        .input(include_str!("input.txt"))
+        .answers(include_str!("answers.toml"))
+        .part(named!(part1))
+        .part(named!(part2))
+}
+
+fn main() -> ExitCode {
+    Runner::create().run(&day())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use advent_of_code::verify;
+    use advent_of_code::aoc_examples;
 
-    #[test]
-    fn example() {
-        let input = include_str!("example.txt");
-        verify!(part1, input, "11");
-        verify!(part2, input, "31");
+    aoc_examples! {
+        example: "example.txt" => { part1: 11, part2: 31 },
     }
 }
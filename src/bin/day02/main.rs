@@ -1,6 +1,6 @@
-use advent_of_code::{create_runner, named, Named, Runner};
+use advent_of_code::{create_day, named, Day, Named, Runner};
 use itertools::Itertools;
-use std::str::Lines;
+use std::{process::ExitCode, str::Lines};
 
 fn parse_report(line: &str) -> Vec<i8> {
     line.split_ascii_whitespace().map(|num| num.parse().unwrap()).collect_vec()
@@ -33,38 +33,38 @@ fn is_dampened_report_safe(report: &[i8]) -> bool {
     is_report_safe(report, None) || (0..report.len()).any(|n| is_report_safe(report, Some(n)))
 }
 
-fn part1(input: Lines) -> String {
+fn part1(input: Lines) -> usize {
     parse_reports(input)
         .into_iter()
         .filter(|r| is_report_safe(r, None))
         .count()
-        .to_string()
 }
 
-fn part2(input: Lines) -> String {
+fn part2(input: Lines) -> usize {
     parse_reports(input)
         .into_iter()
         .filter(|report| is_dampened_report_safe(report))
         .count()
-        .to_string()
 }
 
-fn main() {
-    let input = include_str!("input.txt");
-    let runner: &Runner = create_runner!();
-    runner.run(named!(part1), input);
-    runner.run(named!(part2), input);
+pub fn day() -> Day {
+    create_day!()
+        .input(include_str!("input.txt"))
+        .answers(include_str!("answers.toml"))
+        .part(named!(part1))
+        .part(named!(part2))
+}
+
+fn main() -> ExitCode {
+    Runner::create().run(&day())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use advent_of_code::verify;
+    use advent_of_code::aoc_examples;
 
-    #[test]
-    fn example() {
-        let input = include_str!("example.txt");
-        verify!(part1, input, "2");
-        verify!(part2, input, "4");
+    aoc_examples! {
+        example: "example.txt" => { part1: 2, part2: 4 },
     }
 }
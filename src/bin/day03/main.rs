@@ -1,12 +1,11 @@
-use advent_of_code::{create_runner, named, Named, Runner};
+use advent_of_code::{create_day, named, Day, Named, Runner};
 use lazy_regex::{Regex, Lazy, lazy_regex};
-use std::str::Lines;
+use std::process::ExitCode;
 
 pub static MUL_REGEX: Lazy<Regex> = lazy_regex!(r#"mul\(([0-9]{1,3}),([0-9]{1,3})\)"#);
 
-fn part1(input: Lines) -> String {
-    input
-        .flat_map(|line| MUL_REGEX.captures_iter(line))
+fn part1(input: &str) -> u64 {
+    MUL_REGEX.captures_iter(input)
         .map(|m| {
             m.iter()
                 .skip(1)
@@ -15,14 +14,12 @@ fn part1(input: Lines) -> String {
                 .product::<u64>()
         })
         .sum::<u64>()
-        .to_string()
 }
 
 pub static DO_DONT_MUL_REGEX: Lazy<Regex> = lazy_regex!(r#"do\(\)|don't\(\)|mul\(([0-9]{1,3}),([0-9]{1,3})\)"#);
 
-fn part2(input: Lines) -> String {
-    input
-        .flat_map(|line| DO_DONT_MUL_REGEX.captures_iter(line))
+fn part2(input: &str) -> u64 {
+    DO_DONT_MUL_REGEX.captures_iter(input)
         .fold((true, 0),|(enabled, sum), m| {
             match (enabled, &m[0]) {
                 (_, "do()") => (true, sum),
@@ -37,28 +34,27 @@ fn part2(input: Lines) -> String {
             }
         })
         .1
-        .to_string()
 }
 
-fn main() {
-    let input = include_str!("input.txt");
-    let runner: &Runner = create_runner!();
-    runner.run(named!(part1), input);
-    runner.run(named!(part2), input);
+pub fn day() -> Day {
+    create_day!()
+        .input(include_str!("input.txt"))
+        .answers(include_str!("answers.toml"))
+        .part(named!(part1))
+        .part(named!(part2))
+}
+
+fn main() -> ExitCode {
+    Runner::create().run(&day())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use advent_of_code::verify;
+    use advent_of_code::aoc_examples;
 
-    #[test]
-    fn example() {
-        let input = include_str!("example.txt");
-        let input2 = include_str!("example2.txt");
-        verify!(part1, input, "161");
-        verify!(part1, input2, "161");
-        verify!(part2, input, "161");
-        verify!(part2, input2, "48");
+    aoc_examples! {
+        example: "example.txt" => { part1: 161, part2: 161 },
+        example2: "example2.txt" => { part1: 161, part2: 48 },
     }
 }
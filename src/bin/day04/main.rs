@@ -1,52 +1,18 @@
-use advent_of_code::{create_runner, named, Named, Runner};
+use advent_of_code::{create_day, direction::Direction8, grid::Grid, named, AocError, Day, Named, Runner};
+use glam::IVec2;
 use itertools::Itertools;
-use std::str::Lines;
+use std::{process::ExitCode, str::Lines};
 
-#[derive(PartialEq, Clone, Copy)]
-struct Pos {
-    row: isize,
-    col: isize,
-}
-
-impl Pos {
-    const fn new(row: isize, col: isize) -> Self {
-        Self { row, col }
-    }
-}
-
-impl std::ops::Add for Pos {
-    type Output = Pos;
-
-    fn add(self, rhs: Self) -> Self::Output {
-        Self{ row: self.row + rhs.row, col: self.col + rhs.col }
-    }
-}
+type Pos = IVec2;
 
 struct WordSearch {
-    letters: Vec<Vec<char>>,
-    size: Pos
+    letters: Grid<char>,
 }
 
-const DIRECTIONS: &[Pos] = &[
-    Pos::new(-1, -1),
-    Pos::new(-1, 0),
-    Pos::new(-1, 1),
-    Pos::new(0, -1),
-    Pos::new(0, 1),
-    Pos::new(1, -1),
-    Pos::new(1, 0),
-    Pos::new(1, 1)
-];
-
 impl WordSearch {
-    fn create(input: Lines) -> Self {
-        let letters = input.into_iter()
-            .map(|line| {
-                line.chars().collect_vec()
-            })
-            .collect_vec();
-        let size = Pos::new(letters.len() as isize, letters[0].len() as isize);
-        Self { letters, size }
+    fn create(input: Lines) -> Result<Self, AocError> {
+        let letters = Grid::parse(input, Some)?;
+        Ok(Self { letters })
     }
 
     fn word_count_str(&self, word: &str) -> usize {
@@ -54,16 +20,13 @@ impl WordSearch {
     }
 
     fn word_count_chars(&self, word: &[char]) -> usize {
-        (0..self.size.row)
-            .flat_map(|row| {
-                (0..self.size.col)
-                    .map(move |col| self.word_count_from(word, &Pos::new(row, col)))
-            })
+        self.letters.positions()
+            .map(|pos| self.word_count_from(word, &pos))
             .sum::<usize>()
     }
 
     fn word_count_from(&self, word: &[char], start: &Pos) -> usize {
-        DIRECTIONS.iter().filter(|dir| self.is_word_at(word, start, dir)).count()
+        Direction8::ALL.iter().filter(|dir| self.is_word_at(word, start, &dir.offset())).count()
     }
 
     fn is_word_at(&self, word: &[char], start: &Pos, dir: &Pos) -> bool {
@@ -76,81 +39,82 @@ impl WordSearch {
     }
 
     fn char_at(&self, pos: &Pos) -> Option<char> {
-        self.letters
-            .get(pos.row as usize)
-            .and_then(|row| row.get(pos.col as usize))
-            .copied()
+        self.letters.get(*pos).copied()
     }
 
     fn is_ms(a: char, b: char) -> bool {
         a == 'M' && b == 'S' || a == 'S' && b == 'M'
     }
-    fn is_x_mas(&self, row: usize, col: usize) -> bool {
-        (self.letters[row][col] == 'A')
-            && Self::is_ms(self.letters[row-1][col-1], self.letters[row+1][col+1])
-            && Self::is_ms(self.letters[row-1][col+1], self.letters[row+1][col-1])
+    fn is_x_mas(&self, pos: Pos) -> bool {
+        let at = |x, y| self.letters[pos + Pos::new(x, y)];
+        (at(0, 0) == 'A')
+            && Self::is_ms(at(-1, -1), at(1, 1))
+            && Self::is_ms(at(1, -1), at(-1, 1))
     }
 
     fn x_mas_count(&self) -> usize {
-        (1..(self.size.row as usize) - 1)
-            .map(|row| {
-                (1..(self.size.col as usize) - 1)
-                    .filter(|&col| self.is_x_mas(row, col))
-                    .count()
-            })
-            .sum()
+        let size: Pos = self.letters.size();
+        self.letters.positions()
+            .filter(|pos: &Pos| pos.cmpgt(Pos::ZERO).all() && pos.cmplt(size - 1).all())
+            .filter(|&pos| self.is_x_mas(pos))
+            .count()
     }
 }
 
-fn part1(input: Lines) -> String {
-    WordSearch::create(input).word_count_str("XMAS").to_string()
+fn part1(input: Lines) -> Result<usize, AocError> {
+    Ok(WordSearch::create(input)?.word_count_str("XMAS"))
+}
+
+fn part2(input: Lines) -> Result<usize, AocError> {
+    Ok(WordSearch::create(input)?.x_mas_count())
 }
 
-fn part2(input: Lines) -> String {
-    WordSearch::create(input).x_mas_count().to_string()
+pub fn day() -> Day {
+    create_day!()
+        .input(include_str!("input.txt"))
+        .answers(include_str!("answers.toml"))
+        .part(named!(part1))
+        .part(named!(part2))
 }
 
-fn main() {
-    let input = include_str!("input.txt");
-    let runner: &Runner = create_runner!();
-    runner.run(named!(part1), input);
-    runner.run(named!(part2), input);
+fn main() -> ExitCode {
+    Runner::create().run(&day())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use advent_of_code::verify;
+    use advent_of_code::aoc_examples;
 
     #[test]
     fn char_at() {
-        let ws = WordSearch::create("ab\ncd".lines());
+        let ws = WordSearch::create("ab\ncd".lines()).unwrap();
 
         assert_eq!(ws.char_at(&Pos::new(0, 0)), Some('a'));
-        assert_eq!(ws.char_at(&Pos::new(0, 1)), Some('b'));
-        assert_eq!(ws.char_at(&Pos::new(1, 0)), Some('c'));
+        assert_eq!(ws.char_at(&Pos::new(1, 0)), Some('b'));
+        assert_eq!(ws.char_at(&Pos::new(0, 1)), Some('c'));
         assert_eq!(ws.char_at(&Pos::new(1, 1)), Some('d'));
 
         assert_eq!(ws.char_at(&Pos::new(-1, -1)), None);
-        assert_eq!(ws.char_at(&Pos::new(-1, 0)), None);
-        assert_eq!(ws.char_at(&Pos::new(-1, 1)), None);
-        assert_eq!(ws.char_at(&Pos::new(-1, 2)), None);
-
         assert_eq!(ws.char_at(&Pos::new(0, -1)), None);
-        assert_eq!(ws.char_at(&Pos::new(0, 2)), None);
-
         assert_eq!(ws.char_at(&Pos::new(1, -1)), None);
-        assert_eq!(ws.char_at(&Pos::new(1, 2)), None);
-
         assert_eq!(ws.char_at(&Pos::new(2, -1)), None);
+
+        assert_eq!(ws.char_at(&Pos::new(-1, 0)), None);
         assert_eq!(ws.char_at(&Pos::new(2, 0)), None);
+
+        assert_eq!(ws.char_at(&Pos::new(-1, 1)), None);
         assert_eq!(ws.char_at(&Pos::new(2, 1)), None);
+
+        assert_eq!(ws.char_at(&Pos::new(-1, 2)), None);
+        assert_eq!(ws.char_at(&Pos::new(0, 2)), None);
+        assert_eq!(ws.char_at(&Pos::new(1, 2)), None);
         assert_eq!(ws.char_at(&Pos::new(2, 2)), None);
     }
 
     #[test]
     fn is_word_at() {
-        let ws = WordSearch::create("abc\ndef\nghi".lines());
+        let ws = WordSearch::create("abc\ndef\nghi".lines()).unwrap();
 
         assert!(ws.is_word_at(&['a', 'e', 'i'], &Pos::new(0, 0), &Pos::new(1, 1)));
         assert!(!ws.is_word_at(&['a', 'e', 'x'], &Pos::new(0, 0), &Pos::new(1, 1)));
@@ -165,7 +129,7 @@ mod tests {
 
     #[test]
     fn word_count_str() {
-        let ws = WordSearch::create("abc\ndef\nghi".lines());
+        let ws = WordSearch::create("abc\ndef\nghi".lines()).unwrap();
         assert_eq!(ws.word_count_str("abc"), 1);
         assert_eq!(ws.word_count_str("cba"), 1);
         assert_eq!(ws.word_count_str("aei"), 1);
@@ -173,10 +137,7 @@ mod tests {
         assert_eq!(ws.word_count_str("xyz"), 0);
     }
 
-    #[test]
-    fn example() {
-        let input = include_str!("example.txt");
-        verify!(part1, input, "18");
-        verify!(part2, input, "9");
+    aoc_examples! {
+        example: "example.txt" => { part1: 18, part2: 9 },
     }
 }
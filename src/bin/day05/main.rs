@@ -1,92 +1,75 @@
-use advent_of_code::{create_runner, named, Named, Runner};
+use advent_of_code::{bitset::BitSet, create_day, named, Day, Named, Runner};
 use itertools::Itertools;
-use std::str::Lines;
+use std::{collections::HashMap, process::ExitCode, str::Lines};
 
-type Page = u8;
-const NUM_PAGES: usize = 100;
-
-#[derive(Clone, Copy, Debug, PartialEq)]
-struct PageSet(u128);
-
-impl PageSet {
-    fn empty() -> Self {
-        Self(0)
-    }
-
-    fn from_iter<'a, I>(pages: I) -> Self
-    where
-        I: Iterator<Item = &'a Page>,
-    {
-        Self(pages
-            .map(|p| 1u128 << p)
-            .reduce(|a, b| a | b)
-            .unwrap_or(0)
-        )
-    }
-
-    fn page_bit(page: Page) -> u128 {
-        1 << page
-    }
-
-    fn add(&mut self, page: Page) {
-        self.0 |= Self::page_bit(page)
-    }
-
-    fn remove(&mut self, page: Page) {
-        self.0 &= !Self::page_bit(page)
-    }
-
-    fn is_empty(&self) -> bool {
-        self.0 == 0
-    }
-
-    fn contains_all(&self, other: &PageSet) -> bool {
-        (self.0 & other.0) == other.0
-    }
-
-    fn intersect(&self, other: &PageSet) -> Self {
-        Self(self.0 & other.0)
-    }
-}
+type Page = u32;
+type PageSet = BitSet;
 
 struct Rules {
-    predecessors: [PageSet; NUM_PAGES]
+    /** Each page in the rules has a bit, so page numbers can be anything. */
+    bits: HashMap<Page, usize>,
+    predecessors: Vec<PageSet>,
 }
 
 impl Rules {
-    fn parse_rule(line: &str) -> Option<(u8, u8)> {
+    fn parse_rule(line: &str) -> Option<(Page, Page)> {
         line.split('|')
             .flat_map(|n| n.parse::<Page>().ok())
             .next_tuple()
     }
 
     fn parse(input: &mut Lines) -> Self {
-        let mut predecessors = [PageSet::empty(); NUM_PAGES];
+        let mut rules = Rules{ bits: HashMap::new(), predecessors: Vec::new() };
         for (before, after) in input.map_while(Self::parse_rule) {
-            predecessors[after as usize].add(before);
+            let before = rules.add_page(before);
+            let after = rules.add_page(after);
+            rules.predecessors[after].insert(before);
         }
-        Rules{ predecessors }
+        rules
+    }
+
+    fn add_page(&mut self, page: Page) -> usize {
+        *self.bits.entry(page).or_insert_with(|| {
+            self.predecessors.push(PageSet::new());
+            self.predecessors.len() - 1
+        })
+    }
+
+    /**
+     * The pages of a set, leaving out any without rules, since they can go
+     * anywhere.
+     */
+    fn page_set(&self, pages: &[Page]) -> PageSet {
+        pages.iter().filter_map(|page| self.bits.get(page).copied()).collect()
+    }
+
+    fn predecessors(&self, page: Page) -> Option<&PageSet> {
+        self.bits.get(&page).map(|&bit| &self.predecessors[bit])
     }
 
     fn is_update_valid(&self, update: &Update) -> bool {
-        let all_updated_pages = update.as_set();
-        let mut seen = PageSet::empty();
+        let all_updated_pages = self.page_set(&update.0);
+        let mut seen = PageSet::new();
         for &page in update.0.iter() {
-            if !seen.contains_all(&self.predecessors[page as usize].intersect(&all_updated_pages)) {
-                return false
+            if let Some(&bit) = self.bits.get(&page) {
+                if !seen.is_superset(&self.predecessors[bit].intersection(&all_updated_pages)) {
+                    return false
+                }
+                seen.insert(bit);
             }
-            seen.add(page);
         }
         true
     }
 
     fn reorder_update(&self, update: &Update) -> Update {
         let mut remaining = update.0.clone();
-        let mut remaining_set = update.as_set();
+        let mut remaining_set = self.page_set(&update.0);
         let mut ordered = Vec::new();
-        while let Some((next_index, &next)) = remaining.iter().find_position(|&&p| self.predecessors[p as usize].intersect(&remaining_set).is_empty()) {
+        while let Some((next_index, &next)) = remaining.iter().find_position(|&&p| self.predecessors(p).is_none_or(|pred| pred.intersection(&remaining_set).is_empty())) {
             remaining.swap_remove(next_index);
-            remaining_set.remove(next);
+            if let Some(&bit) = self.bits.get(&next) {
+                remaining_set.remove(bit);
+            }
             ordered.push(next);
         }
         Update(ordered)
@@ -101,10 +84,6 @@ impl Update {
         Self(line.split(',').map(|n| n.parse::<Page>().unwrap()).collect_vec())
     }
 
-    fn as_set(&self) -> PageSet {
-        PageSet::from_iter(self.0.iter())
-    }
-
     fn middle_page(&self) -> Page {
         self.0[self.0.len() / 2]
     }
@@ -116,77 +95,81 @@ fn parse(mut input: Lines) -> (Rules, Vec<Update>) {
     (rules, updates)
 }
 
-fn part1(input: Lines) -> String {
+fn part1(input: Lines) -> u64 {
     let (rules, updates) = parse(input);
     updates.into_iter()
         .filter(|update| rules.is_update_valid(update))
         .map(|update| update.middle_page() as u64)
         .sum::<u64>()
-        .to_string()
 }
 
-fn part2(input: Lines) -> String {
+fn part2(input: Lines) -> u64 {
     let (rules, updates) = parse(input);
     updates.into_iter()
         .filter(|update| !rules.is_update_valid(update))
         .map(|update| rules.reorder_update(&update).middle_page() as u64)
         .sum::<u64>()
-        .to_string()
 }
 
-fn main() {
-    let input = include_str!("input.txt");
-    let runner: &Runner = create_runner!();
-    runner.run(named!(part1), input);
-    runner.run(named!(part2), input);
+pub fn day() -> Day {
+    create_day!()
+        .input(include_str!("input.txt"))
+        .answers(include_str!("answers.toml"))
+        .part(named!(part1))
+        .part(named!(part2))
+}
+
+fn main() -> ExitCode {
+    Runner::create().run(&day())
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::parse;
     use rstest::rstest;
 
     use super::*;
-    use advent_of_code::verify;
+    use advent_of_code::aoc_examples;
 
-    #[test]
-    fn example() {
-        let input = include_str!("example.txt");
-        verify!(part1, input, "143");
-        verify!(part2, input, "123");
+    aoc_examples! {
+        example: "example.txt" => { part1: 143, part2: 123 },
+        large_page_numbers: text "4000000000|7\n7|1000\n\n7,4000000000,1000\n4000000000,12,7" => { part1: 12, part2: 7 },
     }
 
     #[test]
     fn test_parse() {
         let (rules, updates) = parse("1|2\n3|4\n\n1,2,3".lines());
-        assert_eq!(rules.predecessors[2], PageSet::from_iter([1].iter()));
-        assert_eq!(rules.predecessors[4], PageSet::from_iter([3].iter()));
+        assert_eq!(rules.predecessors(2), Some(&rules.page_set(&[1])));
+        assert_eq!(rules.predecessors(4), Some(&rules.page_set(&[3])));
+        assert_eq!(rules.predecessors(1), Some(&PageSet::new()));
+        assert_eq!(rules.predecessors(5), None);
         assert_eq!(updates, vec![Update(vec![1,2,3])]);
     }
 
+    /** Rules between sparse page numbers, which still get dense bits. */
+    fn sparse_rules() -> Rules {
+        parse("4000000000|7\n7|1000".lines()).0
+    }
+
     #[rstest]
-    #[case(&[1, 2], &[1, 2], true)]
-    #[case(&[1, 2], &[1], true)]
-    #[case(&[1, 2], &[2], true)]
-    #[case(&[1, 2], &[3], false)]
-    #[case(&[1, 2], &[2, 3], false)]
-    #[case(&[1, 2], &[1, 2, 3], false)]
+    #[case(&[7, 4000000000], &[7, 4000000000], true)]
+    #[case(&[7, 4000000000], &[4000000000], true)]
+    #[case(&[7, 4000000000], &[1000], false)]
+    #[case(&[7, 4000000000], &[7, 1000], false)]
+    #[case(&[7], &[7, 3], true)]
+    #[case(&[], &[5, 6], true)]
     fn test_page_set_contains_all(#[case] a: &[Page], #[case] b: &[Page], #[case] expected: bool) {
-        assert_eq!(PageSet::from_iter(a.iter()).contains_all(&PageSet::from_iter(b.iter())), expected);
+        let rules = sparse_rules();
+        assert_eq!(rules.page_set(a).is_superset(&rules.page_set(b)), expected);
     }
 
     #[rstest]
-    #[case(&[1, 2], &[1, 2], &[1, 2])]
-    #[case(&[1, 2], &[1], &[1])]
-    #[case(&[1, 2], &[2], &[2])]
-    #[case(&[1, 2], &[3], &[])]
-    #[case(&[1, 2], &[2, 3], &[2])]
-    #[case(&[1, 2], &[1, 2, 3], &[1, 2])]
+    #[case(&[7, 4000000000], &[4000000000, 1000], &[4000000000])]
+    #[case(&[7], &[1000], &[])]
+    #[case(&[7, 12], &[12, 7], &[7])]
+    #[case(&[1000, 4000000000, 7], &[7, 1000], &[1000, 7])]
     fn test_page_set_intersect(#[case] a: &[Page], #[case] b: &[Page], #[case] expected: &[Page]) {
-        assert_eq!(
-            PageSet::from_iter(a.iter()).intersect(&PageSet::from_iter(b.iter())),
-            PageSet::from_iter(expected.iter())
-        );
+        let rules = sparse_rules();
+        assert_eq!(rules.page_set(a).intersection(&rules.page_set(b)), rules.page_set(expected));
     }
 
     #[test]
@@ -1,23 +1,22 @@
-use advent_of_code::{create_runner, named, Named, Runner};
+use advent_of_code::{create_day, direction::Direction4, grid::Grid, named, AocError, Day, Named, Runner};
 use glam::I16Vec2;
-use itertools::Itertools;
-use std::{collections::HashSet, iter::successors, str::Lines};
+use std::{collections::HashSet, iter::successors, process::ExitCode, str::Lines};
 
 type Pos = I16Vec2;
 
 #[derive(PartialEq, Eq, Clone, Copy, Debug, Hash)]
 struct Guard {
     pos: Pos,
-    dir: Direction,
+    dir: Direction4,
 }
 
 impl Guard {
     fn new(pos: Pos) -> Self {
-        Self{ pos, dir: Direction::North }
+        Self{ pos, dir: Direction4::North }
     }
 
     fn forward_pos(&self) -> Pos {
-        self.pos + self.dir.offset()
+        self.pos + self.dir.offset::<Pos>()
     }
     
     fn move_to(&self, pos: Pos) -> Self {
@@ -25,7 +24,7 @@ impl Guard {
     }
 
     fn turn_right(&self) -> Self {
-        Self{pos: self.pos, dir: self.dir.right()}
+        Self{pos: self.pos, dir: self.dir.clockwise()}
     }
 
     fn next<M: LabMap>(self, lab_map: &M) -> Option<Self> {
@@ -40,49 +39,16 @@ impl Guard {
     }
 }
 
-#[derive(PartialEq, Eq, Clone, Copy, Debug, Hash)]
-enum Direction {
-    North,
-    South,
-    West,
-    East,
-}
-
-impl Direction {
-    fn offset(self) -> Pos {
-        match self {
-            Self::North => Pos{x:0, y:-1},
-            Self::East => Pos{x:1, y:0},
-            Self::South => Pos{x:0, y:1},
-            Self::West => Pos{x:-1, y:0},
-        }
-    }
-    fn right(self) -> Self {
-        match self {
-            Self::North => Self::East,
-            Self::East => Self::South,
-            Self::South => Self::West,
-            Self::West => Self::North,
-        }
-    }
-}
-
 trait LabMap {
     fn is_obstacle(&self, pos: &Pos) -> Option<bool>;
 }
 
 struct OriginalMap {
-    obstacles: Vec<Vec<bool>>,
-    size: Pos,
+    obstacles: Grid<bool>,
 }
 
 impl OriginalMap {
-    fn new(obstacles: Vec<Vec<bool>>) -> Self {
-        let size = Pos{x: obstacles[0].len() as i16, y: obstacles.len() as i16};
-        Self{obstacles, size}
-    }
-
-    fn add_obstacle(&self, pos: Pos) -> ModifiedMap {
+    fn add_obstacle(&self, pos: Pos) -> ModifiedMap<'_> {
         ModifiedMap{underlying: self, obstacle: pos}
     }
 
@@ -97,11 +63,7 @@ impl OriginalMap {
 
 impl LabMap for OriginalMap {
     fn is_obstacle(&self, pos: &Pos) -> Option<bool> {
-        if pos.x < 0 || pos.x >= self.size.x || pos.y < 0 || pos.y >= self.size.y {
-            None
-        } else {
-            Some(self.obstacles[pos.y as usize][pos.x as usize])
-        }
+        self.obstacles.get(*pos).copied()
     }
 }
 
@@ -120,27 +82,26 @@ impl LabMap for ModifiedMap<'_> {
     }
 }
 
-fn parse_input(input: Lines) -> (OriginalMap, Guard) {
+fn parse_input(input: Lines) -> Result<(OriginalMap, Guard), AocError> {
     let mut guard_pos: Option<Pos> = None;
-    let obstacles = input.enumerate().map(|(y, line)| {
-        line.chars().enumerate().map(|(x, c)| {
-            match c {
-                '.' => false,
-                '#' => true,
-                '^' => {
-                    guard_pos = Some(Pos{x: x as i16, y: y as i16});
-                    false
-                },
-                _ => panic!("unexpected char {c} at ({x}, {y})")
-            }
-        }).collect_vec()
-    }).collect_vec();
-    (OriginalMap::new(obstacles), Guard::new(guard_pos.expect("didn't find guard")))
+    let obstacles = Grid::parse_with_position(input, |pos, c| {
+        match c {
+            '.' => Some(false),
+            '#' => Some(true),
+            '^' => {
+                guard_pos = Some(pos);
+                Some(false)
+            },
+            _ => None
+        }
+    })?;
+    let guard_pos = guard_pos.ok_or_else(|| AocError::new("didn't find guard"))?;
+    Ok((OriginalMap{obstacles}, Guard::new(guard_pos)))
 }
 
-fn part1(input: Lines) -> String {
-    let (lab_map, guard_start) = parse_input(input);
-    lab_map.find_visited_positions(guard_start).len().to_string()
+fn part1(input: Lines) -> Result<usize, AocError> {
+    let (lab_map, guard_start) = parse_input(input)?;
+    Ok(lab_map.find_visited_positions(guard_start).len())
 }
 
 fn is_guard_stuck_in_loop(guard_start: Guard, lab_map: &ModifiedMap) -> bool {
@@ -154,38 +115,42 @@ fn is_guard_stuck_in_loop(guard_start: Guard, lab_map: &ModifiedMap) -> bool {
     false
 }
 
-fn part2(input: Lines) -> String {
-    let (lab_map, guard) = parse_input(input);
-    lab_map.find_visited_positions(guard)
+fn part2(input: Lines) -> Result<usize, AocError> {
+    let (lab_map, guard) = parse_input(input)?;
+    Ok(lab_map.find_visited_positions(guard)
         .into_iter()
         .filter(|&obstacle| is_guard_stuck_in_loop(guard, &lab_map.add_obstacle(obstacle)))
-        .count()
-        .to_string()
+        .count())
 }
 
-fn main() {
-    let runner: &Runner = create_runner!();
-
-    println!("Fred");
-    let input = include_str!("input.txt");
-    runner.run(named!(part1), input);
-    runner.run(named!(part2), input);
+pub fn day() -> Day {
+    create_day!()
+        .labelled_input("Fred", include_str!("input.txt"))
+        .labelled_input("Jared", include_str!("jared_input.txt"))
+        .answers(include_str!("answers.toml"))
+        .part(named!(part1))
+        .part(named!(part2))
+}
 
-    println!("Jared");
-    let input = include_str!("jared_input.txt");
-    runner.run(named!(part1), input);
-    runner.run(named!(part2), input);
+fn main() -> ExitCode {
+    Runner::create().run(&day())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use advent_of_code::verify;
+    use advent_of_code::aoc_examples;
+
+    aoc_examples! {
+        example: "example.txt" => { part1: 41, part2: 6 },
+    }
 
     #[test]
-    fn example() {
-        let input = include_str!("example.txt");
-        verify!(part1, input, "41");
-        verify!(part2, input, "6");
+    fn parse_error() {
+        assert_eq!(
+            part1("..#\n.x^".lines()),
+            Err(AocError::new("unexpected char x").at(2, 2))
+        );
+        assert_eq!(part1("...".lines()), Err(AocError::new("didn't find guard")));
     }
 }
@@ -1,7 +1,7 @@
-use advent_of_code::{create_runner, named, Named, Runner};
+use advent_of_code::{create_day, named, Day, Named, Runner};
 use itertools::Itertools;
 use num::pow;
-use std::str::Lines;
+use std::{process::ExitCode, str::Lines};
 
 struct Number {
     value: u64,
@@ -64,40 +64,40 @@ impl Equation {
     }
 }
 
-fn part1(input: Lines) -> String {
+fn part1(input: Lines) -> u64 {
     input
         .flat_map(Equation::parse)
         .filter(Equation::can_solve_add_mul)
         .map(|eq| eq.test_value)
         .sum::<u64>()
-        .to_string()
 }
 
-fn part2(input: Lines) -> String {
+fn part2(input: Lines) -> u64 {
     input
         .flat_map(Equation::parse)
         .filter(Equation::can_solve_add_mul_cat)
         .map(|eq| eq.test_value)
         .sum::<u64>()
-        .to_string()
 }
 
-fn main() {
-    let input = include_str!("input.txt");
-    let runner: &Runner = create_runner!();
-    runner.run(named!(part1), input);
-    runner.run(named!(part2), input);
+pub fn day() -> Day {
+    create_day!()
+        .input(include_str!("input.txt"))
+        .answers(include_str!("answers.toml"))
+        .part(named!(part1))
+        .part(named!(part2))
+}
+
+fn main() -> ExitCode {
+    Runner::create().run(&day())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use advent_of_code::verify;
+    use advent_of_code::aoc_examples;
 
-    #[test]
-    fn example() {
-        let input = include_str!("example.txt");
-        verify!(part1, input, "3749");
-        verify!(part2, input, "11387");
+    aoc_examples! {
+        example: "example.txt" => { part1: 3749, part2: 11387 },
     }
 }
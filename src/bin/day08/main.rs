@@ -1,7 +1,7 @@
-use advent_of_code::{create_runner, named, Named, Runner};
+use advent_of_code::{create_day, named, Day, Named, Runner};
 use glam::I16Vec2;
 use itertools::{iterate, Itertools};
-use std::{cmp::max, collections::HashMap, str::Lines};
+use std::{cmp::max, collections::HashMap, process::ExitCode, str::Lines};
 
 type Pos = I16Vec2;
 
@@ -56,30 +56,32 @@ impl AntennaMap {
     }
 }
 
-fn part1(input: Lines) -> String {
-    AntennaMap::parse(input).count_unique_antinodes_in_bounds().to_string()
+fn part1(input: Lines) -> usize {
+    AntennaMap::parse(input).count_unique_antinodes_in_bounds()
 }
 
-fn part2(input: Lines) -> String {
-    AntennaMap::parse(input).count_unique_antinodes_in_bounds_any().to_string()
+fn part2(input: Lines) -> usize {
+    AntennaMap::parse(input).count_unique_antinodes_in_bounds_any()
 }
 
-fn main() {
-    let input = include_str!("input.txt");
-    let runner: &Runner = create_runner!();
-    runner.run(named!(part1), input);
-    runner.run(named!(part2), input);
+pub fn day() -> Day {
+    create_day!()
+        .input(include_str!("input.txt"))
+        .answers(include_str!("answers.toml"))
+        .part(named!(part1))
+        .part(named!(part2))
+}
+
+fn main() -> ExitCode {
+    Runner::create().run(&day())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use advent_of_code::verify;
+    use advent_of_code::aoc_examples;
 
-    #[test]
-    fn example() {
-        let input = include_str!("example.txt");
-        verify!(part1, input, "14");
-        verify!(part2, input, "34");
+    aoc_examples! {
+        example: "example.txt" => { part1: 14, part2: 34 },
     }
 }
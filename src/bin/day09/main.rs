@@ -1,11 +1,32 @@
-use advent_of_code::{create_runner, named, Named, Runner};
+use advent_of_code::{constraint::Constraint, create_day, named, trace, AocError, Day, Named, Runner};
 use itertools::Itertools;
 use core::fmt;
-use std::{collections::VecDeque, fmt::Write, iter::repeat_n, str::Lines};
+use std::{collections::VecDeque, fmt::Write, iter::repeat_n, process::ExitCode};
 
 type FileId = i16;
 const FREE_ID: FileId = -1i16;
 
+/**
+ * The lengths in the disk map, which is a single line of digits.
+ */
+fn digits(input: &[u8]) -> impl Iterator<Item = usize> + '_ {
+    input.iter()
+        .filter(|b| b.is_ascii_digit())
+        .map(|b| (b - b'0') as usize)
+}
+
+/**
+ * Every file needs its own `FileId`, which are numbered from 0.
+ */
+fn file_ids_fit(input: &str) -> Result<(), AocError> {
+    let files = digits(input.as_bytes()).count().div_ceil(2);
+    let max_files = FileId::MAX as usize + 1;
+    if files > max_files {
+        return Err(AocError::new(format!("{files} files exceeds FileId (at most {max_files})")));
+    }
+    Ok(())
+}
+
 struct Disk {
     blocks: Vec<FileId>,
 }
@@ -24,11 +45,10 @@ impl fmt::Display for Disk {
 }
 
 impl Disk {
-    fn parse(line: &str) -> Self {
+    fn parse(input: &[u8]) -> Self {
         let mut id: FileId = FREE_ID;
         let mut is_file = false;
-        let blocks = line.chars()
-            .flat_map(|c| c.to_digit(10).map(|n| n as usize))
+        let blocks = digits(input)
             .flat_map(|length| {
                 is_file = !is_file;
                 if is_file {id += 1};
@@ -47,7 +67,7 @@ impl Disk {
     }
 
     fn compact(&mut self) {
-        #[cfg(test)] println!("Disk={self}");
+        trace!(Step, "Disk={self}");
         let next_free = 0usize;
         let last_used = self.blocks.len();
         while let Some((next_free, last_used)) =
@@ -57,7 +77,33 @@ impl Disk {
         ) {
             self.blocks[next_free] = self.blocks[last_used];
             self.blocks[last_used] = FREE_ID;
-            #[cfg(test)] println!("Disk={self}");
+            trace!(Step, "Disk={self}");
+        }
+    }
+
+    /**
+     * Moves whole files block by block, which is much slower than
+     * [`DiskMap::compact`] but simple enough to serve as its reference.
+     */
+    fn compact_files(&mut self) {
+        trace!(Step, "Disk={self}");
+        let max_id = self.blocks.iter().copied().max().unwrap_or(FREE_ID);
+        let mut end = self.blocks.len();
+        for id in (0..=max_id).rev() {
+            let Some(last) = self.blocks[..end].iter().rposition(|&b| b == id) else {
+                continue;
+            };
+            let start = self.blocks[..last].iter().rposition(|&b| b != id).map_or(0, |i| i + 1);
+            let length = last + 1 - start;
+            end = start;
+            if let Some(free) = self.blocks[..start]
+                .windows(length)
+                .position(|w| w.iter().all(|&b| b == FREE_ID))
+            {
+                self.blocks[free..free + length].fill(id);
+                self.blocks[start..start + length].fill(FREE_ID);
+                trace!(Step, "Disk={self}");
+            }
         }
     }
 
@@ -65,16 +111,16 @@ impl Disk {
         self.blocks
             .iter()
             .enumerate()
-            .filter(|&(_, &id)| (id != FREE_ID))
+            .filter(|&(_, &id)| id != FREE_ID)
             .map(|(i, &id)| id as usize * i)
             .sum()
     }
 }
 
-fn part1(mut input: Lines) -> String {
-    let mut disk = Disk::parse(input.next().expect("one line of input"));
+fn part1(input: &[u8]) -> usize {
+    let mut disk = Disk::parse(input);
     disk.compact();
-    disk.checksum().to_string()
+    disk.checksum()
 }
 
 #[derive(Clone, Copy)]
@@ -111,12 +157,12 @@ fn sum_of_n(n: usize) -> usize {
 }
 
 impl DiskMap {
-    fn parse(line: &str) -> Self {
+    fn parse(input: &[u8]) -> Self {
         let mut start: usize = 0;
         let mut is_file = true;
         let mut files: Vec<Chunk> = Vec::new();
         let mut empty: VecDeque<Chunk> = VecDeque::new();
-        for length in line.chars().flat_map(|c| c.to_digit(10).map(|n| n as usize)) {
+        for length in digits(input) {
             if is_file {
                 files.push(Chunk{start, length});
             } else {
@@ -150,11 +196,11 @@ impl DiskMap {
     }
 
     fn compact(&mut self) {
-        #[cfg(test)] println!("DiskMap={self}");
+        trace!(Step, "DiskMap={self}");
         for id in (0..self.files.len()).rev() {
             if let Some(start) = self.get_compacted_file_start(self.files[id]) {
                 self.files[id].start = start;
-                #[cfg(test)] println!("DiskMap={self}");
+                trace!(Step, "DiskMap={self}");
             }
         }
     }
@@ -167,28 +213,47 @@ impl DiskMap {
     }
 }
 
-fn part2(mut input: Lines) -> String {
-    let mut disk_map = DiskMap::parse(input.next().expect("one line of input"));
+fn part2(input: &[u8]) -> usize {
+    let mut disk_map = DiskMap::parse(input);
     disk_map.compact();
-    disk_map.checksum().to_string()
+    disk_map.checksum()
+}
+
+fn part2_blocks(input: &[u8]) -> usize {
+    let mut disk = Disk::parse(input);
+    disk.compact_files();
+    disk.checksum()
 }
 
-fn main() {
-    let input = include_str!("input.txt");
-    let runner: &Runner = create_runner!();
-    runner.run(named!(part1), input);
-    runner.run(named!(part2), input);
+pub fn day() -> Day {
+    create_day!()
+        .input(include_str!("input.txt"))
+        .answers(include_str!("answers.toml"))
+        .constraint(Constraint::create(file_ids_fit))
+        .part(named!(part1))
+        .part(named!(part2))
+        .variant(named!(part2_blocks))
+}
+
+fn main() -> ExitCode {
+    Runner::create().run(&day())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use advent_of_code::verify;
+    use advent_of_code::{property::{self, int, vec_of, Property}, aoc_examples};
+
+    aoc_examples! {
+        example: "example.txt" => { part1: 1928, part2: 2858, part2_blocks: 2858 },
+    }
 
     #[test]
-    fn example() {
-        let input = include_str!("example.txt");
-        verify!(part1, input, "1928");
-        verify!(part2, input, "2858");
+    fn compact_files_matches_blocks() {
+        let files = vec_of((int(1..=9), int(0..=9)), 1..=30);
+        Property::create(files).check(|files| {
+            let input = files.iter().map(|(file, free)| format!("{file}{free}")).join("");
+            property::equal(part2(input.as_bytes()), part2_blocks(input.as_bytes()))
+        });
     }
 }
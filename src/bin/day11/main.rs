@@ -1,12 +1,10 @@
-use advent_of_code::{create_runner, named, Named, Runner};
+use advent_of_code::{create_day, named, AocError, Day, Named, Params, Runner};
 use itertools::{iterate, Itertools};
 use num::Integer;
-use std::{collections::HashMap, str::Lines};
+use std::{collections::HashMap, process::ExitCode};
 
-fn parse_numbers(mut input: Lines) -> Vec<u64> {
-    input.next()
-        .expect("at least one line")
-        .split_ascii_whitespace()
+fn parse_numbers(input: &str) -> Vec<u64> {
+    input.split_ascii_whitespace()
         .map(|n| n.parse().expect("numbers only"))
         .collect_vec()
 }
@@ -47,7 +45,7 @@ fn blink_once(stones: &HashMap<u64, usize>) -> HashMap<u64, usize> {
     next
 }
 
-fn blink_many(input: Lines, count: usize) -> usize {
+fn blink_many(input: &str, count: usize) -> usize {
     iterate(build_histogram(parse_numbers(input)), blink_once)
         .nth(count)
         .unwrap()
@@ -56,25 +54,34 @@ fn blink_many(input: Lines, count: usize) -> usize {
 
 }
 
-fn part1(input: Lines) -> String {
-    blink_many(input, 25).to_string()
+const PART1_PARAMS: &[(&str, &str)] = &[("blinks", "25")];
+
+fn part1(input: &str, params: &Params) -> Result<usize, AocError> {
+    Ok(blink_many(input, params.get("blinks")?))
 }
 
-fn part2(input: Lines) -> String {
-    blink_many(input, 75).to_string()
+const PART2_PARAMS: &[(&str, &str)] = &[("blinks", "75")];
+
+fn part2(input: &str, params: &Params) -> Result<usize, AocError> {
+    Ok(blink_many(input, params.get("blinks")?))
 }
 
-fn main() {
-    let input = include_str!("input.txt");
-    let runner: &Runner = create_runner!();
-    runner.run(named!(part1), input);
-    runner.run(named!(part2), input);
+pub fn day() -> Day {
+    create_day!()
+        .input(include_str!("input.txt"))
+        .answers(include_str!("answers.toml"))
+        .part_with_params(named!(part1), PART1_PARAMS)
+        .part_with_params(named!(part2), PART2_PARAMS)
+}
+
+fn main() -> ExitCode {
+    Runner::create().run(&day())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use advent_of_code::verify;
+    use advent_of_code::aoc_examples;
 
     #[test]
     fn test_split_even_len_str() {
@@ -87,9 +94,8 @@ mod tests {
         assert_eq!(split_even_len_str(512072), Some([512, 72]));
     }
 
-    #[test]
-    fn example() {
-        let input = include_str!("example.txt");
-        verify!(part1, input, "55312");
+    aoc_examples! {
+        six_blinks: "example.txt" with Params::create(PART1_PARAMS).with("blinks", 6) => { part1: 22 },
+        example: "example.txt" with Params::create(PART1_PARAMS) => { part1: 55312 },
     }
 }
@@ -1,24 +1,9 @@
-use advent_of_code::{create_runner, named, Named, Runner};
+use advent_of_code::{create_day, direction::{Direction4, Direction8}, grid::Grid, Answer, AocError, Day, Params, Runner, Solution};
 use glam::I16Vec2;
-use itertools::Itertools;
-use std::{collections::{HashMap, VecDeque}, str::Lines};
+use std::{collections::{HashMap, VecDeque}, process::ExitCode, str::Lines};
 
 type Pos = I16Vec2;
 
-const DIRECTIONS: [Pos; 4] = [
-    Pos::new(1, 0),
-    Pos::new(0, -1),
-    Pos::new(-1, 0),
-    Pos::new(0, 1),
-];
-
-const CORNERS: [Pos; 4] = [
-    Pos::new(1, -1),
-    Pos::new(-1, -1),
-    Pos::new(-1, 1),
-    Pos::new(1, 1),
-];
-
 #[derive(Clone)]
 struct Plot {
     kind: char,
@@ -41,19 +26,13 @@ impl Plot {
 }
 
 struct GardenPlotMap {
-    plots: Vec<Vec<Plot>>,
-    size: Pos,
+    plots: Grid<Plot>,
 }
 
 impl GardenPlotMap {
-    fn parse(input: Lines) -> Self {
-        let plots = input.into_iter()
-            .map(|line| {
-                line.chars().map(Plot::new).collect_vec()
-            })
-            .collect_vec();
-        let size = Pos::new(plots[0].len() as i16, plots.len() as i16);
-        Self{plots, size}
+    fn parse(input: Lines) -> Result<Self, AocError> {
+        let plots = Grid::parse(input, |c| Some(Plot::new(c)))?;
+        Ok(Self{plots})
     }
 
     /**
@@ -82,13 +61,15 @@ impl GardenPlotMap {
      *    ```
      */
     fn count_corners(&self, pos: Pos, kind: char) -> usize {
-        let in_adj = DIRECTIONS
-            .map(|off| {
-                self.get(pos + off).is_some_and(|p| p.kind == kind)
+        let in_adj = Direction4::ALL
+            .map(|dir| {
+                self.plots.get(pos + dir.offset::<Pos>()).is_some_and(|p| p.kind == kind)
             });
-        let in_corner = CORNERS
-            .map(|off| {
-                self.get(pos + off).is_some_and(|p| p.kind == kind)
+        // the corner between each direction and the next one clockwise
+        let in_corner = Direction4::ALL
+            .map(|dir| {
+                let corner = Direction8::from(dir).clockwise();
+                self.plots.get(pos + corner.offset::<Pos>()).is_some_and(|p| p.kind == kind)
             });
         [(0, 1), (1, 2), (2, 3), (3, 0)].into_iter()
             .filter(|&(a, b)| {
@@ -97,26 +78,6 @@ impl GardenPlotMap {
             .count()
     }
 
-    fn get_unsafe_mut(&mut self, pos: Pos) -> &mut Plot {
-        &mut self.plots[pos.y as usize][pos.x as usize]
-    }
-
-    fn get_unsafe(&self, pos: Pos) -> &Plot {
-        &self.plots[pos.y as usize][pos.x as usize]
-    }
-
-    fn in_bounds(&self, pos: &Pos) -> bool {
-        pos.x >= 0 && pos.x < self.size.x && pos.y >= 0 && pos.y < self.size.y
-    }
-
-    fn get_mut(&mut self, pos: Pos) -> Option<&mut Plot> {
-        self.in_bounds(&pos).then(|| self.get_unsafe_mut(pos))
-    }
-
-    fn get(&self, pos: Pos) -> Option<&Plot> {
-        self.in_bounds(&pos).then(|| self.get_unsafe(pos))
-    }
-
     /**
      * Find a region which contains the position `pos` and return a description
      * of its shape.
@@ -156,16 +117,16 @@ impl GardenPlotMap {
      * corners which can be computed by looking at a position an its neighbors.
      */
     fn get_region(&mut self, pos: Pos) -> Region {
-        let region_kind = self.get_unsafe_mut(pos).kind;
+        let region_kind = self.plots[pos].kind;
         let mut traverse: VecDeque<Pos> = VecDeque::new();
         let mut area: usize = 0;
         let mut internal_sides: usize = 0;
         let mut corners: usize = 0;
         traverse.push_back(pos);
         while let Some(pos) = traverse.pop_front() {
-            for offset in DIRECTIONS.iter() {
-                let new_pos = pos + offset;
-                if let Some(new_plot) = self.get_mut(new_pos) {
+            for dir in Direction4::ALL {
+                let new_pos = pos + dir.offset::<Pos>();
+                if let Some(new_plot) = self.plots.get_mut(new_pos) {
                     if new_plot.kind == region_kind {
                         internal_sides += 1;
                         if new_plot.first_visit() {
@@ -183,13 +144,10 @@ impl GardenPlotMap {
 
     fn get_regions(&mut self) -> HashMap<char, Vec<Region>> {
         let mut regions: HashMap<char, Vec<Region>> = HashMap::new();
-        for y in 0..self.size.y {
-            for x in 0..self.size.x {
-                let pos = Pos::new(x, y);
-                let plot = self.get_unsafe_mut(pos);
-                if plot.first_visit() {
-                    regions.entry(plot.kind).or_default().push(self.get_region(pos));
-                } 
+        for pos in self.plots.positions::<Pos>() {
+            let plot = &mut self.plots[pos];
+            if plot.first_visit() {
+                regions.entry(plot.kind).or_default().push(self.get_region(pos));
             }
         }
         regions
@@ -213,40 +171,51 @@ impl Region {
     }
 }
 
-fn part1(input: Lines) -> String {
-    let regions = GardenPlotMap::parse(input).get_regions();
-    regions
-        .values()
-        .flat_map(|rs| rs.iter().map(|r| r.price()))
-        .sum::<usize>()
-        .to_string()
+struct GardenGroups;
+
+impl Solution for GardenGroups {
+    type Parsed = HashMap<char, Vec<Region>>;
+
+    fn parse(input: Lines, _params: &Params) -> Result<Self::Parsed, AocError> {
+        Ok(GardenPlotMap::parse(input)?.get_regions())
+    }
+
+    fn part1(regions: &Self::Parsed) -> impl Into<Answer> {
+        regions
+            .values()
+            .flat_map(|rs| rs.iter().map(|r| r.price()))
+            .sum::<usize>()
+    }
+
+    fn part2(regions: &Self::Parsed) -> impl Into<Answer> {
+        regions
+            .values()
+            .flat_map(|rs| rs.iter().map(|r| r.bulk_price()))
+            .sum::<usize>()
+    }
 }
 
-fn part2(input: Lines) -> String {
-    let regions = GardenPlotMap::parse(input).get_regions();
-    regions
-        .values()
-        .flat_map(|rs| rs.iter().map(|r| r.bulk_price()))
-        .sum::<usize>()
-        .to_string()
+pub fn day() -> Day {
+    create_day!()
+        .input(include_str!("input.txt"))
+        .answers(include_str!("answers.toml"))
+        .solution::<GardenGroups>()
 }
 
-fn main() {
-    let input = include_str!("input.txt");
-    let runner: &Runner = create_runner!();
-    runner.run(named!(part1), input);
-    runner.run(named!(part2), input);
+fn main() -> ExitCode {
+    Runner::create().run(&day())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use advent_of_code::verify;
+    use advent_of_code::aoc_examples;
+    use itertools::Itertools;
 
     #[test]
     fn get_regions() {
         assert_eq!(
-            GardenPlotMap::parse(include_str!("example.txt").lines()).get_regions(),
+            GardenPlotMap::parse(include_str!("example.txt").lines()).unwrap().get_regions(),
             HashMap::from([
                 ('C', vec![
                     Region{area: 14, perimeter: 28, sides: 22},
@@ -269,6 +238,7 @@ mod tests {
 
     fn get_region_sides(input: Lines) -> HashMap<char, Vec<usize>> {
         GardenPlotMap::parse(input)
+            .unwrap()
             .get_regions()
             .into_iter()
             .map(|(kind, regions)| {
@@ -328,10 +298,7 @@ AAAAAA";
         );
     }
 
-    #[test]
-    fn example() {
-        let input = include_str!("example.txt");
-        verify!(part1, input, "1930");
-        verify!(part2, input, "1206");
+    aoc_examples! {
+        example: "example.txt" => GardenGroups { part1: 1930, part2: 1206 },
     }
 }
@@ -1,8 +1,8 @@
-use advent_of_code::{create_runner, named, Named, Runner};
+use advent_of_code::{create_day, named, Day, Named, Runner};
 use glam::{DMat2, DVec2, U64Vec2};
 use itertools::Itertools;
 use lazy_regex::{lazy_regex, Captures, Lazy, Regex};
-use std::str::Lines;
+use std::{process::ExitCode, str::Lines};
 
 type Pos = U64Vec2;
 
@@ -76,38 +76,43 @@ impl Machine {
             DMat2::from_cols(b, A.col(1)).determinant(),
             DMat2::from_cols(A.col(0), b).determinant()
         ) / A.determinant();
-        (presses.trunc() == presses).then_some(presses.dot(Self::COST) as u64)
+        // A button can't be pressed a negative or fractional number of times.
+        (presses.trunc() == presses && presses.min_element() >= 0.0)
+            .then_some(presses.dot(Self::COST) as u64)
     }
 }
 
-fn part1(input: Lines) -> String {
+fn part1(input: Lines) -> u64 {
     Machine::parse_all(input)
         .into_iter()
         .flat_map(|m| m.min_cost())
         .sum::<u64>()
-        .to_string()
 }
 
-fn part2(input: Lines) -> String {
+fn part2(input: Lines) -> u64 {
     Machine::parse_all(input)
         .into_iter()
         .map(|m| m.offset_prize(10000000000000))
         .flat_map(|m| m.min_cost())
         .sum::<u64>()
-        .to_string()
 }
 
-fn main() {
-    let input = include_str!("input.txt");
-    let runner: &Runner = create_runner!();
-    runner.run(named!(part1), input);
-    runner.run(named!(part2), input);
+pub fn day() -> Day {
+    create_day!()
+        .input(include_str!("input.txt"))
+        .answers(include_str!("answers.toml"))
+        .part(named!(part1))
+        .part(named!(part2))
+}
+
+fn main() -> ExitCode {
+    Runner::create().run(&day())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use advent_of_code::verify;
+    use advent_of_code::{property::{self, blocks, int, Property}, aoc_examples};
 
     #[test]
     fn parse() {
@@ -152,9 +157,61 @@ mod tests {
     }
 
     #[test]
-    fn example() {
-        let input = include_str!("example.txt");
-        verify!(part1, input, "480");
-        verify!(part2, input, "875318608908");
+    fn test_min_cost_negative_presses() {
+        // Cramer's rule solves this with -1 presses of A and 2 of B, which
+        // used to be priced as a win.
+        let machine = Machine{a: Pos::new(2, 1), b: Pos::new(1, 2), prize: Pos::new(0, 3)};
+        assert_eq!(machine.min_cost(), None);
+        let machine = Machine{a: Pos::new(2, 1), b: Pos::new(1, 2), prize: Pos::new(4, 5)};
+        assert_eq!(machine.min_cost(), Some(3 + 2));
+    }
+
+    fn brute_force_min_cost(machine: &Machine) -> Option<u64> {
+        (0..=machine.prize.x / machine.a.x)
+            .filter_map(|a| {
+                let pressed = machine.a * a;
+                if pressed.y > machine.prize.y {
+                    return None;
+                }
+                let rest = machine.prize - pressed;
+                let b = rest.x / machine.b.x;
+                (machine.b * b == rest).then_some(3 * a + b)
+            })
+            .min()
+    }
+
+    type Generated = ((i64, i64), (i64, i64), (i64, i64), (i64, i64));
+
+    fn generated_machine(&((ax, ay), (bx, by), (na, nb), (dx, dy)): &Generated) -> Machine {
+        let a = Pos::new(ax as u64, ay as u64);
+        let b = Pos::new(bx as u64, by as u64);
+        Machine{a, b, prize: a * na as u64 + b * nb as u64 + Pos::new(dx as u64, dy as u64)}
+    }
+
+    #[test]
+    fn min_cost_matches_brute_force() {
+        let button = || (int(1..=10), int(1..=10));
+        // Prizes near a reachable one, which are often unreachable.
+        let machine = (button(), button(), (int(0..=20), int(0..=20)), (int(0..=3), int(0..=3)));
+        let machines = blocks(machine, 1..=4, |generated| {
+            let m = generated_machine(generated);
+            format!(
+                "Button A: X+{}, Y+{}\nButton B: X+{}, Y+{}\nPrize: X={}, Y={}",
+                m.a.x, m.a.y, m.b.x, m.b.y, m.prize.x, m.prize.y
+            )
+        });
+        Property::create(machines).check(|input| {
+            let machines = input.blocks.iter().map(generated_machine).collect_vec();
+            // Collinear buttons have many solutions, and never appear in inputs.
+            if machines.iter().any(|m| m.a.x * m.b.y == m.a.y * m.b.x) {
+                return Ok(());
+            }
+            let expected = machines.iter().flat_map(brute_force_min_cost).sum::<u64>();
+            property::equal(part1(input.text.lines()), expected)
+        });
+    }
+
+    aoc_examples! {
+        example: "example.txt" => { part1: 480, part2: 875318608908u64 },
     }
 }
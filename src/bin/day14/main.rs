@@ -1,8 +1,8 @@
-use advent_of_code::{create_runner, named, Named, Runner};
+use advent_of_code::{create_day, named, Answer, AocError, Day, Named, Params, Runner};
 use glam::IVec2;
 use itertools::Itertools;
 use lazy_regex::{lazy_regex, Lazy, Regex};
-use std::{cmp::Ordering, iter::successors, str::Lines};
+use std::{cmp::Ordering, iter::successors, process::ExitCode, str::Lines};
 type Pos = IVec2;
 type Comp = i32;
 struct Robot {
@@ -13,15 +13,20 @@ struct Robot {
 pub static ROBOT_REGEX: Lazy<Regex> = lazy_regex!(r#"p=([0-9]+),([0-9]+) v=(-?[0-9]+),(-?[0-9]+)"#);
 
 impl Robot {
-    fn parse(line: &str) -> Self {
+    fn parse(line_number: usize, line: &str) -> Result<Self, AocError> {
         let (px, py, vx, vy) = ROBOT_REGEX.captures(line)
-            .expect("robot match")
+            .ok_or_else(|| AocError::new(format!("expected p=x,y v=x,y but found {line}")).at_line(line_number))?
             .iter()
             .skip(1)
-            .map(|n| n.expect("group").as_str().parse().expect("robot values numeric"))
+            .map(|n| {
+                let n = n.expect("group");
+                n.as_str().parse().map_err(|e| AocError::new(format!("robot value {e}")).at(line_number, n.start() + 1))
+            })
+            .collect::<Result<Vec<Comp>, _>>()?
+            .into_iter()
             .collect_tuple()
             .expect("4 groups");
-        Self{pos: Pos::new(px, py), vel: Pos::new(vx, vy)}
+        Ok(Self{pos: Pos::new(px, py), vel: Pos::new(vx, vy)})
     }
 }
 
@@ -31,15 +36,15 @@ struct RestroomMap {
 }
 
 impl RestroomMap {
-    fn parse(mut input: Lines) -> Self {
-        let (size_x, size_y) = input.next().expect("size required")
-            .split_whitespace()
-            .flat_map(|n| n.parse().ok())
-            .collect_tuple()
-            .expect("size has 2 numeric");
-        let size = Pos::new(size_x, size_y);
-        let robots = input.map(Robot::parse).collect_vec();
-        Self{size, robots}
+    fn parse(input: Lines, params: &Params) -> Result<Self, AocError> {
+        let size = Pos::new(params.get("width")?, params.get("height")?);
+        if size.x <= 0 || size.y <= 0 {
+            return Err(AocError::new(format!("size must be positive but is {size}")));
+        }
+        let robots = input.enumerate()
+            .map(|(index, line)| Robot::parse(index + 1, line))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self{size, robots})
     }
     
     fn initial_positions(&self) -> Vec<Pos> {
@@ -68,6 +73,16 @@ impl RestroomMap {
         ).count()
     }
 
+    fn picture(&self, positions: &[Pos]) -> Answer {
+        let mut rows = vec![vec!['.'; self.size.x as usize]; self.size.y as usize];
+        for &pos in positions {
+            // robots which haven't moved yet may start outside the map
+            let pos = self.wrap_position(pos);
+            rows[pos.y as usize][pos.x as usize] = '#';
+        }
+        Answer::picture(rows.into_iter().map(String::from_iter))
+    }
+
     fn quadrant_component(component: Comp, mid: Comp, weight: usize) -> Option<usize> {
         match component.cmp(&mid) {
             Ordering::Less => Some(0),
@@ -94,10 +109,12 @@ impl RestroomMap {
     }
 }
 
-fn part1(input: Lines) -> String {
-    let map = RestroomMap::parse(input);
+const PARAMS: &[(&str, &str)] = &[("width", "101"), ("height", "103")];
+
+fn part1(input: Lines, params: &Params) -> Result<usize, AocError> {
+    let map = RestroomMap::parse(input, params)?;
     let positions = map.advance_positions(&map.initial_positions(), 100);
-    map.safety_factor(&positions).to_string()
+    Ok(map.safety_factor(&positions))
 }
 
 /**
@@ -106,33 +123,69 @@ fn part1(input: Lines) -> String {
  *
  *  is equivalent to getting to a state where no robots overlap.
  */
-fn part2(input: Lines) -> String {
-    let map = RestroomMap::parse(input);
-    map.advance_positions_until_no_overlap().to_string()
+fn part2(input: Lines, params: &Params) -> Result<usize, AocError> {
+    let map = RestroomMap::parse(input, params)?;
+    Ok(map.advance_positions_until_no_overlap())
+}
+
+/**
+ * The picture the robots arrange themselves into, to see that [`part2`] found it.
+ */
+fn tree(input: Lines, params: &Params) -> Result<Answer, AocError> {
+    let map = RestroomMap::parse(input, params)?;
+    let seconds = map.advance_positions_until_no_overlap();
+    Ok(map.picture(&map.advance_positions(&map.initial_positions(), seconds)))
+}
+
+pub fn day() -> Day {
+    create_day!()
+        .input(include_str!("input.txt"))
+        .answers(include_str!("answers.toml"))
+        .part_with_params(named!(part1), PARAMS)
+        .part_with_params(named!(part2), PARAMS)
+        .part_with_params(named!(tree), PARAMS)
 }
 
-fn main() {
-    let input = include_str!("input.txt");
-    let runner: &Runner = create_runner!();
-    runner.run(named!(part1), input);
-    runner.run(named!(part2), input);
+fn main() -> ExitCode {
+    Runner::create().run(&day())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use advent_of_code::verify;
+    use advent_of_code::aoc_examples;
+
+    fn example_params() -> Params {
+        Params::create(PARAMS).with("width", 11).with("height", 7)
+    }
 
     #[test]
     fn wrap_position() {
-        let map = RestroomMap::parse("5 5".lines());
+        let params = Params::create(PARAMS).with("width", 5).with("height", 5);
+        let map = RestroomMap::parse("".lines(), &params).unwrap();
         assert_eq!(map.wrap_position(Pos::new(-1, -1)), Pos::new(4, 4));
     }
 
+    // part 2 not testable
+    aoc_examples! {
+        example: "example.txt" with example_params() => { part1: 12 },
+        picture: text "p=0,0 v=1,0\np=0,0 v=0,1" with Params::create(PARAMS).with("width", 3).with("height", 2) => { tree: Answer::picture([".#.", "#.."]) },
+    }
+
+    #[test]
+    fn picture_outside_map() {
+        let params = Params::create(PARAMS).with("width", 3).with("height", 2);
+        let map = RestroomMap::parse("".lines(), &params).unwrap();
+        assert_eq!(map.picture(&[Pos::new(4, 3), Pos::new(-1, 0)]), Answer::picture(["..#", ".#."]));
+    }
+
     #[test]
-    fn example() {
-        let input = include_str!("example.txt");
-        verify!(part1, input, "12");
-        // part 2 not testable
+    fn parse_error() {
+        let error = part1("p=0,4 v=3,-3\np=6,3 v=-1".lines(), &example_params()).unwrap_err();
+        assert_eq!((error.line, error.column), (Some(2), None));
+        let error = part1("p=99999999999,4 v=3,-3".lines(), &example_params()).unwrap_err();
+        assert_eq!((error.line, error.column), (Some(1), Some(3)));
+        let error = part1("".lines(), &example_params().with("width", "x")).unwrap_err();
+        assert_eq!(error.message, "parameter width=x: invalid digit found in string");
     }
 }
@@ -1,7 +1,7 @@
-use advent_of_code::{create_runner, named, Named, Runner};
+use advent_of_code::{create_day, direction::Direction4, grid::Grid, named, trace, AocError, Day, Named, Runner};
 use glam::I16Vec2;
 use itertools::Itertools;
-use std::{collections::{HashSet, VecDeque}, str::Lines};
+use std::{collections::{HashSet, VecDeque}, fmt, process::ExitCode, str::Lines};
 
 #[derive(Clone, Copy, Debug, PartialEq)]
 enum BoxHalf {
@@ -29,14 +29,14 @@ enum Cell {
 }
 
 impl Cell {
-    fn parse(c: char) -> Self {
+    fn parse(c: char) -> Option<Self> {
         match c {
-            '.' => Cell::Empty,
-            'O' => Cell::Box(None),
-            '[' => Cell::Box(Some(BoxHalf::Left)),
-            ']' => Cell::Box(Some(BoxHalf::Right)),
-            '#' => Cell::Wall,
-            u => panic!("unexpected cell character {u}")
+            '.' => Some(Cell::Empty),
+            'O' => Some(Cell::Box(None)),
+            '[' => Some(Cell::Box(Some(BoxHalf::Left))),
+            ']' => Some(Cell::Box(Some(BoxHalf::Right))),
+            '#' => Some(Cell::Wall),
+            _ => None,
         }
     }
 
@@ -47,7 +47,6 @@ impl Cell {
         }
     }
 
-    #[cfg(test)]
     fn display(&self) -> char {
         match self {
             Cell::Empty => '.',
@@ -59,104 +58,97 @@ impl Cell {
     }
 }
 
-#[derive(Clone, Copy, Debug)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-impl Direction {
-    fn parse(c: char) -> Self {
-        match c {
-            '^' => Direction::Up,
-            'v' => Direction::Down,
-            '<' => Direction::Left,
-            '>' => Direction::Right,
-            u => panic!("unexpected direction character {u}")
-        }
-    }
-
-    #[cfg(test)]
-    fn display(&self) -> char {
-        match self {
-            Direction::Up => '^',
-            Direction::Down => 'v',
-            Direction::Left => '<',
-            Direction::Right => '>',
-        }
-    }
-
-    fn offset(self) -> Pos {
-        match self {
-            Direction::Up => Pos::new(0, -1),
-            Direction::Down => Pos::new(0, 1),
-            Direction::Left => Pos::new(-1, 0),
-            Direction::Right => Pos::new(1, 0),
-        }
-    }
-}
-
 struct WarehouseMap {
-    cells: Vec<Vec<Cell>>,
+    cells: Grid<Cell>,
     robot: Pos,
-    moves: Vec<Direction>,
+    moves: Vec<Direction4>,
 }
 
 type Pos = I16Vec2;
 type PosComp = i16;
 
+impl fmt::Display for WarehouseMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.cells.rows().enumerate() {
+            let line = row.iter()
+                .enumerate()
+                .map(|(x, c)| {
+                    if self.robot == Pos::new(x as PosComp, y as PosComp) {
+                        '@'
+                    } else {
+                        c.display()
+                    }
+                })
+                .collect::<String>();
+            writeln!(f, "{line}")?;
+        }
+        Ok(())
+    }
+}
+
 impl WarehouseMap {
-    fn parse_narrow(input: Lines) -> Self {
-        Self::parse(input, |c| [Some(c), None])
+    fn parse_narrow(input: Lines) -> Result<Self, AocError> {
+        Self::parse(input, |c| Some([Some(c), None]))
     }
 
-    fn parse_wide(input: Lines) -> Self {
+    fn parse_wide(input: Lines) -> Result<Self, AocError> {
         Self::parse(input, |c: char| {
             match c {
-                '#' => [Some('#'), Some('#')],
-                'O' => [Some('['), Some(']')],
-                '.' => [Some('.'), Some('.')],
-                '@' => [Some('@'), Some('.')],
-                u => panic!("unxepected char {u}"),
+                '#' => Some([Some('#'), Some('#')]),
+                'O' => Some([Some('['), Some(']')]),
+                '.' => Some([Some('.'), Some('.')]),
+                '@' => Some([Some('@'), Some('.')]),
+                _ => None,
             }
         })
     }
-    fn parse(mut input: Lines, expand: impl Fn(char) -> [Option<char>; 2]) -> Self {
-        let mut robot = Pos::new(0, 0);
-        let cells = input
+
+    /**
+     * Parses the map, with each character turned into the one or two cells
+     * of `expand`, followed by the robot's moves.
+     */
+    fn parse(input: Lines, expand: impl Fn(char) -> Option<[Option<char>; 2]>) -> Result<Self, AocError> {
+        let mut lines = input.enumerate();
+        let map = lines
             .by_ref()
-            .take_while(|line| !line.is_empty())
-            .enumerate()
-            .map(|(y, line)| {
+            .take_while(|(_, line)| !line.is_empty())
+            .map(|(row, line)| {
                 line.chars()
-                .flat_map(|c| expand(c).into_iter().flatten())
-                .enumerate().map(|(x, c)| {
-                    if c == '@' {
-                        robot = Pos::new(x as PosComp, y as PosComp);
-                        Cell::parse('.')
-                    } else {
-                        Cell::parse(c)
-                    }
-                })
-                .collect_vec()
+                    .enumerate()
+                    .map(|(column, c)| {
+                        expand(c).ok_or_else(|| AocError::new(format!("unexpected char {c}")).at(row + 1, column + 1))
+                    })
+                    .flatten_ok()
+                    .flatten_ok()
+                    .collect::<Result<String, _>>()
             })
-            .collect_vec();
-        let moves = input
-            .flat_map(|line| {
-                line.chars().map(Direction::parse)
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut robot = None;
+        let cells = Grid::parse_with_position(map.iter().map(String::as_str), |pos, c| {
+            if c == '@' {
+                robot = Some(pos);
+                Cell::parse('.')
+            } else {
+                Cell::parse(c)
+            }
+        })?;
+        let robot = robot.ok_or_else(|| AocError::new("didn't find robot"))?;
+        let moves = lines
+            .flat_map(|(row, line)| {
+                line.chars().enumerate().map(move |(column, c)| {
+                    Direction4::from_arrow(c).ok_or_else(|| AocError::new(format!("unexpected move {c}")).at(row + 1, column + 1))
+                })
             })
-            .collect_vec();
-        Self{cells, robot, moves}
+            .try_collect()?;
+        Ok(Self{cells, robot, moves})
     }
 
     fn get_cell(&self, pos: &Pos) -> Cell {
-        self.cells[pos.y as usize][pos.x as usize]
+        self.cells[*pos]
     }
 
     fn set_cell(&mut self, pos: Pos, c: Cell) {
-        self.cells[pos.y as usize][pos.x as usize] = c;
+        self.cells[pos] = c;
     }
 
     fn find_empty_cell(&self, pos: Pos, offset: &Pos) -> Option<Pos> {
@@ -167,8 +159,8 @@ impl WarehouseMap {
         }
     }
 
-    fn find_moveable_wide_blocks_horz(&self, pos: Pos, dir: Direction, blocks: &mut HashSet<Pos>) -> bool {
-        let offset = dir.offset() * 2;
+    fn find_moveable_wide_blocks_horz(&self, pos: Pos, dir: Direction4, blocks: &mut HashSet<Pos>) -> bool {
+        let offset = dir.offset::<Pos>() * 2;
         let box_offset = if offset.x < 0 { -BoxHalf::RIGHT_OFFSET } else { Pos::ZERO };
         let mut traverse = VecDeque::new();
         traverse.push_back(pos);
@@ -188,8 +180,8 @@ impl WarehouseMap {
         true
     }
 
-    fn find_moveable_wide_blocks_vert(&self, pos: Pos, dir: Direction, blocks: &mut HashSet<Pos>) -> bool {
-        let offset = dir.offset();
+    fn find_moveable_wide_blocks_vert(&self, pos: Pos, dir: Direction4, blocks: &mut HashSet<Pos>) -> bool {
+        let offset = dir.offset::<Pos>();
         let mut traverse = VecDeque::new();
         traverse.push_back(pos);
         while let Some(pos) = traverse.pop_front() {
@@ -210,8 +202,8 @@ impl WarehouseMap {
         true
     }
 
-    fn move_wide_blocks(&mut self, pos: Pos, dir: Direction) -> bool {
-        let offset = dir.offset();
+    fn move_wide_blocks(&mut self, pos: Pos, dir: Direction4) -> bool {
+        let offset = dir.offset::<Pos>();
         let mut blocks: HashSet<Pos> = HashSet::new();
         let do_move = if offset.x != 0 {
             self.find_moveable_wide_blocks_horz(pos, dir, &mut blocks)
@@ -232,8 +224,8 @@ impl WarehouseMap {
         do_move
     }
 
-    fn step(&mut self, dir: Direction) {
-        let offset = dir.offset();
+    fn step(&mut self, dir: Direction4) {
+        let offset = dir.offset::<Pos>();
         let target = self.robot + offset;
         match self.get_cell(&target) {
             Cell::Empty => self.robot = target,
@@ -253,82 +245,63 @@ impl WarehouseMap {
         };
     }
 
-    #[cfg(test)]
-    fn print_map(&self, maybe_dir: Option<Direction>) {
-        match maybe_dir {
-            Some(dir) => println!("\nMove {c}:", c=dir.display()),
-            None => println!("Initial state:"),
-        }
-        for (y, row) in self.cells.iter().enumerate() {
-            let line = row.iter()
-                .enumerate()
-                .map(|(x, c)| {
-                    if self.robot == Pos::new(x as PosComp, y as PosComp) {
-                        '@'
-                    } else {
-                        c.display()
-                    }
-                })
-                .collect::<String>();
-            println!("{line}");
-        }
-    }
-
     fn simulate(&mut self) {
-        #[cfg(test)] self.print_map(None);
+        trace!(Step, "Initial state:\n{self}");
         for dir in self.moves.clone() {
             self.step(dir);
-            #[cfg(test)] self.print_map(Some(dir));
+            trace!(Step, "Move {dir}:\n{self}");
         }
     }
 
     fn box_gps_coordinates(&self) -> usize {
-        self.cells.iter()
-            .enumerate()
-            .flat_map(|(y, row)| {
-                row.iter()
-                    .enumerate()
-                    .filter(|(_, &cell)| cell.is_box_locatable())
-                    .map(move |(x, _)| y * 100 + x)
-            })
+        self.cells.iter::<Pos>()
+            .filter(|(_, &cell)| cell.is_box_locatable())
+            .map(|(pos, _)| pos.y as usize * 100 + pos.x as usize)
             .sum()
     }
 }
 
-fn part1(input: Lines) -> String {
-    let mut map = WarehouseMap::parse_narrow(input);
+fn part1(input: Lines) -> Result<usize, AocError> {
+    let mut map = WarehouseMap::parse_narrow(input)?;
     map.simulate();
-    map.box_gps_coordinates().to_string()
+    Ok(map.box_gps_coordinates())
 }
 
-fn part2(input: Lines) -> String {
-    let mut map = WarehouseMap::parse_wide(input);
+fn part2(input: Lines) -> Result<usize, AocError> {
+    let mut map = WarehouseMap::parse_wide(input)?;
     map.simulate();
-    map.box_gps_coordinates().to_string()
+    Ok(map.box_gps_coordinates())
 }
 
-fn main() {
-    let input = include_str!("input.txt");
-    let runner: &Runner = create_runner!();
-    runner.run(named!(part1), input);
-    runner.run(named!(part2), input);
+pub fn day() -> Day {
+    create_day!()
+        .input(include_str!("input.txt"))
+        .answers(include_str!("answers.toml"))
+        .part(named!(part1))
+        .part(named!(part2))
+}
+
+fn main() -> ExitCode {
+    Runner::create().run(&day())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use advent_of_code::verify;
+    use advent_of_code::aoc_examples;
 
-    #[test]
-    fn example() {
-        let input = include_str!("example.txt");
-        verify!(part1, input, "10092");
-        verify!(part2, input, "9021");
+    aoc_examples! {
+        example: "example.txt" => { part1: 10092, part2: 9021 },
+        example_small: "example_small.txt" => { part2: 618 },
     }
 
     #[test]
-    fn example_small() {
-        let input = include_str!("example_small.txt");
-        verify!(part2, input, "618");
+    fn parse_error() {
+        let error = |input: &str| part2(input.lines()).unwrap_err().to_string();
+        assert_eq!(error("#####\n#@.x#\n#####\n\n<>"), "line 2, column 4: unexpected char x");
+        assert_eq!(error("#####\n#@.#\n#####\n\n<>"), "line 2: expected 10 characters like the first line but found 8");
+        assert_eq!(error("#####\n#@..#\n#####\n\n<\n>x"), "line 6, column 2: unexpected move x");
+        assert_eq!(error("#####\n#...#\n#####\n\n<>"), "didn't find robot");
+        assert_eq!(part1("###\n#@x\n###\n\n<".lines()).unwrap_err().to_string(), "line 2, column 3: unexpected char x");
     }
 }
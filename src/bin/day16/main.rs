@@ -1,222 +1,150 @@
-use advent_of_code::{create_runner, named, Named, Runner};
+use advent_of_code::{create_day, direction::Direction4, grid::Grid, pathfinding::{dijkstra, Paths}, Answer, AocError, Day, Params, Runner, Solution};
+use glam::U8Vec2;
 use itertools::Itertools;
-use std::{collections::{HashSet, VecDeque}, iter::repeat_n, ops::Add, str::Lines};
-
-#[derive(Clone, Copy)]
-enum Direction {
-    East = 0,
-    North = 1,
-    West = 2,
-    South = 3,
-}
-const DIRECTIONS: [Direction; 4] = [
-    Direction::East,
-    Direction::North,
-    Direction::West,
-    Direction::South,
-];
-
-impl Direction {
-    fn cw(&self) -> Self {
-        match self {
-            Direction::East => Direction::South,
-            Direction::South => Direction::West,
-            Direction::West => Direction::North,
-            Direction::North => Direction::East,
-        }
-    }
-
-    fn ccw(&self) -> Self {
-        match self {
-            Direction::East => Direction::North,
-            Direction::North => Direction::West,
-            Direction::West => Direction::South,
-            Direction::South => Direction::East,
-        }
-    }
-
-    fn flip(&self) -> Self {
-        match self {
-            Direction::East => Direction::West,
-            Direction::North => Direction::South,
-            Direction::West => Direction::East,
-            Direction::South => Direction::North,
-        }
-    }
-}
-
-#[derive(Clone, Debug, Hash, PartialEq, Eq)]
-struct Pos {
-    x: usize,
-    y: usize,
-}
+use std::{process::ExitCode, str::Lines};
 
-impl Add<Direction> for Pos {
-    type Output = Pos;
-
-    fn add(mut self, rhs: Direction) -> Self::Output {
-        match rhs {
-            Direction::East => self.x += 1,
-            Direction::North => self.y -= 1,
-            Direction::West => self.x -= 1,
-            Direction::South => self.y += 1,
-        }
-        self
-    }
-}
+type Pos = U8Vec2;
+type State = (Pos, Direction4);
 
 struct Maze {
-    tiles: Vec<Vec<bool>>,
-    size: Pos,
+    tiles: Grid<bool>,
     start: Pos,
-    end: Pos, 
+    end: Pos,
 }
 
 impl Maze {
-    fn parse(input: Lines) -> Self {
-        let mut start = Pos{x: 0, y: 0};
-        let mut end = Pos{x: 0, y: 0};
-        let tiles = input.into_iter()
-            .enumerate()
-            .map(|(y, row)| {
-                row.chars().enumerate()
-                    .map(|(x, c)| {
-                        match c {
-                            '#' => false,
-                            '.' => true,
-                            'S' => {
-                                start = Pos{x, y};
-                                true
-                            },
-                            'E' => {
-                                end = Pos{x, y};
-                                true
-                            },
-                            u => panic!("unexpected char {u}")
-                        }
-                    })
-                    .collect_vec()
-            })
-            .collect_vec();
-        let size = Pos{x: tiles[0].len(), y: tiles.len()};
-        Self{tiles, size, start, end}
+    fn parse(input: Lines) -> Result<Self, AocError> {
+        let mut start = None;
+        let mut end = None;
+        let tiles = Grid::parse_with_position(input, |pos, c| {
+            match c {
+                '#' => Some(false),
+                '.' => Some(true),
+                'S' => {
+                    start = Some(pos);
+                    Some(true)
+                },
+                'E' => {
+                    end = Some(pos);
+                    Some(true)
+                },
+                _ => None
+            }
+        })?;
+        Ok(Self{
+            tiles,
+            start: start.ok_or_else(|| AocError::new("didn't find start"))?,
+            end: end.ok_or_else(|| AocError::new("didn't find end"))?,
+        })
     }
-    
+
     fn is_empty(&self, pos: &Pos) -> bool {
-        self.tiles[pos.y][pos.x]
+        self.tiles.get(*pos) == Some(&true)
+    }
+
+    /**
+     * Moving forward scores 1 and turning in place scores 1000.
+     */
+    fn moves(&self, &(pos, dir): &State) -> Vec<(State, Score)> {
+        let mut moves = vec![
+            ((pos, dir.clockwise()), 1000),
+            ((pos, dir.counter_clockwise()), 1000),
+        ];
+        let forward = pos.wrapping_add(dir.offset());
+        if self.is_empty(&forward) {
+            moves.push(((forward, dir), 1));
+        }
+        moves
+    }
+
+    /**
+     * Every best path from the start, facing east, to the end, facing any
+     * direction, or an error when the end can't be reached.
+     */
+    fn best_paths(&self) -> Result<BestPaths, AocError> {
+        let paths = dijkstra((self.start, Direction4::East), |state| self.moves(state));
+        let (score, ends) = paths.cheapest(|&(pos, _)| pos == self.end)
+            .ok_or_else(|| AocError::new("can't reach end"))?;
+        Ok(BestPaths{paths, score, ends})
     }
 }
 
 type Score = u64;
 
-struct Solver<'a> {
-    maze: &'a Maze,
-    best: Vec<Vec<[Option<Score>; 4]>>,
+/**
+ * The best score of reaching the end, each way of facing which reaches it
+ * with that score, and the paths which do.
+ */
+struct BestPaths {
+    paths: Paths<State, Score>,
+    score: Score,
+    ends: Vec<State>,
 }
 
-impl<'a> Solver<'a> {
-    fn new(maze: &'a Maze) -> Self {
-        let best = repeat_n(
-            repeat_n([None; 4], maze.size.x)
-                .collect_vec(),
-            maze.size.y
-        ).collect_vec();
-        Self{maze, best}
+impl BestPaths {
+    fn tiles(&self) -> usize {
+        self.paths.on_paths_to(self.ends.iter().copied())
+            .into_iter()
+            .map(|(pos, _)| pos)
+            .unique()
+            .count()
     }
+}
 
-    fn set_better(&mut self, pos: &Pos, dir: Direction, score: Score) -> bool {
-        let existing = &mut self.best[pos.y][pos.x][dir as usize];
-        if existing.is_none_or(|old| score < old) {
-            *existing = Some(score);
-            true
-        } else {
-            false
-        }
-    }
+struct ReindeerMaze;
 
-    fn best_score_at(&self, pos: &Pos) -> Score {
-        *self.best[pos.y][pos.x].iter().flatten().min().expect("didn't reach end")
-    }
+impl Solution for ReindeerMaze {
+    type Parsed = BestPaths;
 
-    fn min_score_to_end(&mut self) -> Score {
-        let mut traverse = VecDeque::new();
-        traverse.push_back((self.maze.start.clone(), Direction::East, 0));
-        while let Some((pos, dir, score)) = traverse.pop_front() {
-            if self.maze.is_empty(&pos) && self.set_better(&pos, dir, score) {
-                traverse.push_back((pos.clone() + dir, dir, score + 1));
-                traverse.push_back((pos.clone(), dir.cw(), score + 1000));
-                traverse.push_back((pos, dir.ccw(), score + 1000));
-            }
-        }
-        self.best_score_at(&self.maze.end)
+    fn parse(input: Lines, _params: &Params) -> Result<Self::Parsed, AocError> {
+        Maze::parse(input)?.best_paths()
     }
 
-    fn find_best_path_tiles(&self, pos: &Pos, dir: Direction, score: Score, tiles: &mut HashSet<Pos>) {
-        if self.best[pos.y][pos.x][dir as usize] == Some(score) {
-            tiles.insert(pos.clone());
-            if score >= 1 {
-                self.find_best_path_tiles(&(pos.clone() + dir.flip()), dir, score - 1, tiles);
-            }
-            if score >= 1000 {
-                self.find_best_path_tiles(pos, dir.cw(), score - 1000, tiles);
-                self.find_best_path_tiles(pos, dir.ccw(), score - 1000, tiles);
-            }
-        }
+    fn part1(best: &Self::Parsed) -> impl Into<Answer> {
+        best.score
     }
 
-    fn tiles_on_best_path(&mut self) -> usize {
-        let best = self.min_score_to_end();
-        let mut visited: HashSet<Pos> = HashSet::new();
-        for &dir in DIRECTIONS.iter() {
-            self.find_best_path_tiles(&self.maze.end, dir, best, &mut visited);
-        }
-        visited.len()
+    fn part2(best: &Self::Parsed) -> impl Into<Answer> {
+        best.tiles()
     }
 }
 
-fn part1(input: Lines) -> String {
-    let maze = Maze::parse(input);
-    Solver::new(&maze).min_score_to_end().to_string()
-}
-
-fn part2(input: Lines) -> String {
-    let maze = Maze::parse(input);
-    Solver::new(&maze).tiles_on_best_path().to_string()
+pub fn day() -> Day {
+    create_day!()
+        .input(include_str!("input.txt"))
+        .answers(include_str!("answers.toml"))
+        .solution::<ReindeerMaze>()
 }
 
-fn main() {
-    let input = include_str!("input.txt");
-    let runner: &Runner = create_runner!();
-    runner.run(named!(part1), input);
-    runner.run(named!(part2), input);
+fn main() -> ExitCode {
+    Runner::create().run(&day())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use advent_of_code::verify;
+    use advent_of_code::aoc_examples;
 
     #[test]
     fn parse() {
-        let maze = Maze::parse(include_str!("example.txt").lines());
-        assert!(!maze.is_empty(&Pos{x: 0, y: 0}));
-        assert!(maze.is_empty(&Pos{x: 1, y: 1}));
-        assert_eq!(maze.size, Pos{x: 15, y: 15});
-        assert_eq!(maze.start, Pos{x: 1, y: 13});
-        assert_eq!(maze.end, Pos{x: 13, y: 1});
+        let maze = Maze::parse(include_str!("example.txt").lines()).unwrap();
+        assert!(!maze.is_empty(&Pos::new(0, 0)));
+        assert!(maze.is_empty(&Pos::new(1, 1)));
+        assert_eq!(maze.tiles.size::<Pos>(), Pos::new(15, 15));
+        assert_eq!(maze.start, Pos::new(1, 13));
+        assert_eq!(maze.end, Pos::new(13, 1));
     }
 
     #[test]
-    fn example() {
-        let input = include_str!("example.txt");
-        verify!(part1, input, "7036");
-        verify!(part2, input, "45");
+    fn parse_error() {
+        let error = |input: &str| ReindeerMaze::parse(input.lines(), &Params::default()).err().map(|e| e.message);
+        assert_eq!(error("#####\n#S..#\n#####"), Some("didn't find end".to_owned()));
+        assert_eq!(error("#####\n#..E#\n#####"), Some("didn't find start".to_owned()));
+        assert_eq!(error("#####\n#S#E#\n#####"), Some("can't reach end".to_owned()));
     }
 
-    #[test]
-    fn example2() {
-        let input = include_str!("example2.txt");
-        verify!(part1, input, "11048");
-        verify!(part2, input, "64");
+    aoc_examples! {
+        example: "example.txt" => ReindeerMaze { part1: 7036, part2: 45 },
+        example2: "example2.txt" => ReindeerMaze { part1: 11048, part2: 64 },
     }
 }
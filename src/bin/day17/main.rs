@@ -1,7 +1,7 @@
-use advent_of_code::{create_runner, named, Named, Runner};
+use advent_of_code::{create_day, named, Day, Named, Runner};
 use itertools::Itertools;
 use num::pow;
-use std::str::Lines;
+use std::{process::ExitCode, str::Lines};
 
 type Integer = u64;
 type Registers = [Integer; 3];
@@ -184,7 +184,6 @@ fn simulate_step(a: Integer) -> u8 {
 /**
  * Reference implementation created by directly translating input instructions.
  */
-#[cfg(test)]
 fn simulate(mut a: Integer) -> Vec<u8> {
     let mut output = Vec::new();
     while a != 0 {
@@ -213,27 +212,39 @@ fn find_a_register(instructions: &[u8]) -> Option<Integer> {
         .find_map(|a| reverse_simulate(&reversed_instructions, a))
 }
 
-fn part2(input: Lines) -> String {
+/**
+ * Uses [`simulate`] instead of running the program, so only works for `input.txt`.
+ */
+fn part1_simulated(input: Lines) -> String {
     let computer = Computer::parse(input);
-    find_a_register(&computer.instructions).unwrap().to_string()
+    simulate(computer.registers[0]).iter().join(",")
 }
 
-fn main() {
-    let input = include_str!("input.txt");
-    let runner: &Runner = create_runner!();
-    runner.run(named!(part1), input);
-    runner.run(named!(part2), input);
+fn part2(input: Lines) -> Integer {
+    let computer = Computer::parse(input);
+    find_a_register(&computer.instructions).unwrap()
+}
+
+pub fn day() -> Day {
+    create_day!()
+        .input(include_str!("input.txt"))
+        .answers(include_str!("answers.toml"))
+        .part(named!(part1))
+        .variant(named!(part1_simulated))
+        .part(named!(part2))
+}
+
+fn main() -> ExitCode {
+    Runner::create().run(&day())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use advent_of_code::verify;
+    use advent_of_code::aoc_examples;
 
-    #[test]
-    fn example() {
-        let input = include_str!("example.txt");
-        verify!(part1, input, "4,6,3,5,6,3,5,2,1,0");
+    aoc_examples! {
+        example: "example.txt" => { part1: "4,6,3,5,6,3,5,2,1,0" },
     }
 
     #[test]
@@ -1,63 +1,49 @@
-use advent_of_code::{create_runner, named, Named, Runner};
+use advent_of_code::{constraint::Constraint, create_day, grid::Grid, named, pathfinding::bfs_to, Answer, AocError, Day, Named, Params, Runner};
 use glam::I8Vec2;
-use itertools::{repeat_n, Itertools};
-use std::{collections::VecDeque, str::Lines};
+use itertools::Itertools;
+use std::{process::ExitCode, str::Lines};
 
 type Pos = I8Vec2;
-const DIRECTIONS: [Pos; 4] = [
-    Pos::new(1, 0),
-    Pos::new(0, -1),
-    Pos::new(-1, 0),
-    Pos::new(0, 1),
-];
 
-fn pos_to_string(pos: &Pos) -> String {
-    format!("{x},{y}", x=pos.x, y=pos.y)
+fn pos_to_answer(pos: &Pos) -> Answer {
+    Answer::coordinates(pos.x, pos.y)
 }
 
 struct Input {
-    part1_size: usize,
     coordinates: Vec<Pos>,
     size: Pos,
 }
 
 impl Input {
-    fn parse(mut input: Lines) -> Self {
-        let part1_size = input.by_ref()
-            .next()
-            .expect("first line")
-            .parse::<usize>()
-            .expect("part1_size numeric");
-        let coordinates = input
-            .map(|line| {
-                let (x, y) = line.split(',').map(|n| n.parse::<i8>().expect("numeric")).collect_tuple().expect("2 components");
-                Pos{x, y}
+    fn parse(input: Lines) -> Result<Self, AocError> {
+        let coordinates: Vec<Pos> = input
+            .enumerate()
+            .map(|(index, line)| {
+                let coordinate = |n: &str| n.parse::<i8>().ok().filter(|n| (0..i8::MAX).contains(n));
+                line.split(',')
+                    .map(coordinate)
+                    .collect_tuple()
+                    .and_then(|(x, y)| Some(Pos::new(x?, y?)))
+                    .ok_or_else(|| AocError::new(format!("expected x,y but found {line}")).at_line(index + 1))
             })
-            .collect_vec();
-        let max_x = coordinates.iter().map(|c| c.x).max().expect("at least one x");
-        let max_y = coordinates.iter().map(|c| c.y).max().expect("at least one y");
+            .try_collect()?;
+        let max_x = coordinates.iter().map(|c| c.x).max().ok_or_else(|| AocError::new("no bytes fall"))?;
+        let max_y = coordinates.iter().map(|c| c.y).max().unwrap_or_default();
         let size = Pos::new(max_x + 1, max_y + 1);
-        Self{part1_size, coordinates, size}
+        Ok(Self{coordinates, size})
     }
 }
 
 struct Space<'a> {
     input: &'a Input,
-    corrupted: Vec<Vec<bool>>,
-    steps: Vec<Vec<Option<usize>>>,
+    corrupted: Grid<bool>,
 }
 
 impl<'a> Space<'a> {
     fn new(input: &'a Input) -> Self {
-        let corrupted = repeat_n(
-            repeat_n(false, input.size.x as usize).collect_vec(),
-            input.size.y as usize
-        ).collect_vec();
-        let steps = repeat_n(
-            repeat_n(None, input.size.x as usize).collect_vec(),
-            input.size.y as usize
-        ).collect_vec();
-        Self{input, corrupted, steps}
+        let (width, height) = (input.size.x as usize, input.size.y as usize);
+        let corrupted = Grid::new(width, height, false);
+        Self{input, corrupted}
     }
     fn corrupt_first_n(&mut self, n: usize) {
         for pos in self.input.coordinates.iter().take(n) {
@@ -65,79 +51,87 @@ impl<'a> Space<'a> {
         }
     }
     fn corrupt(&mut self, pos: &Pos) {
-        self.corrupted[pos.y as usize][pos.x as usize] = true;
+        self.corrupted[*pos] = true;
     }
     fn is_safe(&self, pos: &Pos) -> bool {
-        pos.x >= 0 && pos.x < self.input.size.x
-            && pos.y >= 0 && pos.y < self.input.size.y
-            && !self.corrupted[pos.y as usize][pos.x as usize]
+        !self.corrupted[*pos]
     }
-    fn set_better(&mut self, pos: &Pos, steps: usize) -> bool {
-        let old_steps = &mut self.steps[pos.y as usize][pos.x as usize];
-        if old_steps.is_none_or(|old| steps < old) {
-            *old_steps = Some(steps);
-            true
-        } else {
-            false
-        }
+    fn min_steps(&self) -> Option<usize> {
+        let exit = self.input.size - 1;
+        let steps = Grid::new(self.corrupted.width(), self.corrupted.height(), None);
+        bfs_to(Pos::ZERO, &exit, steps, |&pos| {
+            self.corrupted.neighbors4(pos).filter(|new_pos| self.is_safe(new_pos))
+        })
     }
-    fn min_steps(&mut self) -> Option<usize> {
-        let mut traverse = VecDeque::new();
-        if self.steps[0][0].is_some() {
-            self.steps.iter_mut().for_each(|row| row.fill(None));
-        }
-        traverse.push_back((Pos::new(0, 0), 0));
-        while let Some((pos, steps)) = traverse.pop_front() {
-            if self.set_better(&pos, steps) {
-                for dir in DIRECTIONS.iter() {
-                    let new_pos = pos + dir;
-                    if self.is_safe(&new_pos) {
-                        traverse.push_back((new_pos, steps + 1));
-                    }
-                }
-            }
-        }
-        self.steps[self.input.size.y as usize - 1][self.input.size.x as usize - 1]
+}
+
+/**
+ * How many bytes have fallen before part 1 finds its path, which part 2
+ * also uses as a head start.
+ */
+const PARAMS: &[(&str, &str)] = &[("bytes", "1024")];
+
+fn fallen_bytes(input: &Input, params: &Params) -> Result<usize, AocError> {
+    let bytes = params.get("bytes")?;
+    if bytes > input.coordinates.len() {
+        return Err(AocError::new(format!("only {} bytes fall, not {bytes}", input.coordinates.len())));
     }
+    Ok(bytes)
 }
 
-fn part1(input: Lines) -> String {
-    let input = Input::parse(input);
+fn part1(input: Lines, params: &Params) -> Result<usize, AocError> {
+    let input = Input::parse(input)?;
+    let bytes = fallen_bytes(&input, params)?;
     let mut space = Space::new(&input);
-    space.corrupt_first_n(input.part1_size);
-    space.min_steps().expect("exit visited").to_string()
+    space.corrupt_first_n(bytes);
+    let steps = space.min_steps().ok_or_else(|| AocError::new(format!("the first {bytes} bytes block the exit")))?;
+    Ok(steps)
 }
 
-fn part2(input: Lines) -> String {
-    let input = Input::parse(input);
+fn part2(input: Lines, params: &Params) -> Result<Answer, AocError> {
+    let input = Input::parse(input)?;
+    let bytes = fallen_bytes(&input, params)?;
     let mut space = Space::new(&input);
-    space.corrupt_first_n(input.part1_size);
-    pos_to_string(
-        input.coordinates[input.part1_size..].iter()
+    space.corrupt_first_n(bytes);
+    input.coordinates[bytes..].iter()
         .find(|pos| {
             space.corrupt(pos);
             space.min_steps().is_none()
         })
-        .expect("some blocked")
-    )
+        .map(pos_to_answer)
+        .ok_or_else(|| AocError::new("no byte blocks the exit"))
+}
+
+pub fn day() -> Day {
+    create_day!()
+        .input(include_str!("input.txt"))
+        .answers(include_str!("answers.toml"))
+        // The size of the space is one more than the largest coordinate.
+        .constraint(Constraint::numbers_at_most(i8::MAX as u64 - 1, "I8Vec2"))
+        .part_with_params(named!(part1), PARAMS)
+        .part_with_params(named!(part2), PARAMS)
 }
 
-fn main() {
-    let input = include_str!("input.txt");
-    let runner: &Runner = create_runner!();
-    runner.run(named!(part1), input);
-    runner.run(named!(part2), input);
+fn main() -> ExitCode {
+    Runner::create().run(&day())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use advent_of_code::verify;
+    use advent_of_code::aoc_examples;
+
+    aoc_examples! {
+        example: "example.txt" with Params::create(PARAMS).with("bytes", 12) => { part1: 22, part2: Answer::coordinates(6, 1) },
+    }
 
     #[test]
-    fn example() {
-        let input = include_str!("example.txt");
-        verify!(part1, input, "22");
-        verify!(part2, input, "6,1");
+    fn parse_error() {
+        let error = |input: &str, bytes| part1(input.lines(), &Params::create(PARAMS).with("bytes", bytes)).unwrap_err();
+        assert_eq!(error("-5,3\n1,1", 1).to_string(), "line 1: expected x,y but found -5,3");
+        assert_eq!(error("1,1\n2", 1).to_string(), "line 2: expected x,y but found 2");
+        assert_eq!(error("", 0).message, "no bytes fall");
+        assert_eq!(error("0,1\n1,0", 2).message, "the first 2 bytes block the exit");
+        assert_eq!(day().validate("-5,3\n1,1").unwrap_err().message, "number -5 is below I8Vec2 (at least 0)");
     }
 }
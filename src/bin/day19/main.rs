@@ -1,6 +1,6 @@
-use advent_of_code::{create_runner, named, Named, Runner};
+use advent_of_code::{create_day, Answer, AocError, Day, Params, Runner, Solution};
 use itertools::Itertools;
-use std::{iter::repeat, str::Lines};
+use std::{iter::repeat_n, process::ExitCode, str::Lines};
 
 struct Input {
     patterns: Vec<String>,
@@ -8,9 +8,9 @@ struct Input {
 }
 
 impl Input {
-    fn parse(mut input: Lines) -> Self {
+    fn parse(mut input: Lines) -> Result<Self, AocError> {
         let patterns = input.next()
-            .expect("patterns on first line")
+            .ok_or_else(|| AocError::new("expected patterns on the first line"))?
             .split(", ")
             .sorted()
             .map(|s| s.to_owned())
@@ -18,12 +18,12 @@ impl Input {
         let designs = input.skip(1)
             .map(|s| s.to_owned())
             .collect_vec();
-        Self{patterns, designs}
+        Ok(Self{patterns, designs})
     }
     
     fn count_ways_to_display_design(&self, design: &str) -> usize {
         // matches[i] stores the number of ways to display design[..i]
-        let mut matches: Vec<usize> = Vec::from_iter(repeat(0).take(design.len() + 1));
+        let mut matches: Vec<usize> = Vec::from_iter(repeat_n(0, design.len() + 1));
         matches[0] = 1;
         for i in 0..design.len() {
             for pattern in &self.patterns {
@@ -48,30 +48,41 @@ impl Input {
     }
 }
 
-fn part1(input: Lines) -> String {
-    Input::parse(input).count_can_display().to_string()
+struct LinenLayout;
+
+impl Solution for LinenLayout {
+    type Parsed = Input;
+
+    fn parse(input: Lines, _params: &Params) -> Result<Self::Parsed, AocError> {
+        Input::parse(input)
+    }
+
+    fn part1(input: &Self::Parsed) -> impl Into<Answer> {
+        input.count_can_display()
+    }
+
+    fn part2(input: &Self::Parsed) -> impl Into<Answer> {
+        input.count_ways_to_display()
+    }
 }
 
-fn part2(input: Lines) -> String {
-    Input::parse(input).count_ways_to_display().to_string()
+pub fn day() -> Day {
+    create_day!()
+        .input(include_str!("input.txt"))
+        .answers(include_str!("answers.toml"))
+        .solution::<LinenLayout>()
 }
 
-fn main() {
-    let input = include_str!("input.txt");
-    let runner: &Runner = create_runner!();
-    runner.run(named!(part1), input);
-    runner.run(named!(part2), input);
+fn main() -> ExitCode {
+    Runner::create().run(&day())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use advent_of_code::verify;
+    use advent_of_code::aoc_examples;
 
-    #[test]
-    fn example() {
-        let input = include_str!("example.txt");
-        verify!(part1, input, "6");
-        verify!(part2, input, "16");
+    aoc_examples! {
+        example: "example.txt" => LinenLayout { part1: 6, part2: 16 },
     }
 }
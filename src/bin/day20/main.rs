@@ -1,10 +1,16 @@
-use advent_of_code::{create_runner, named, Named, Runner};
+use advent_of_code::{constraint::Constraint, create_day, direction::Direction4, grid::Grid, named, pathfinding::bfs_costs, AocError, Day, Named, Params, Runner};
 use glam::U8Vec2;
-use itertools::{repeat_n, Itertools};
-use std::{collections::{HashMap, VecDeque}, str::Lines};
+use itertools::Itertools;
+use std::{collections::HashMap, process::ExitCode, str::Lines};
 
 type Pos = U8Vec2;
 
+/**
+ * The largest racetrack whose positions fit in a `Pos`, even when a cheat
+ * reaches 20 cells past a track cell, which is at most `size - 2`.
+ */
+const MAX_SIZE: usize = u8::MAX as usize + 2 - 20;
+
 #[derive(Clone, Copy, Debug, PartialEq)]
 enum CellKind {
     Track,
@@ -13,64 +19,57 @@ enum CellKind {
 }
 
 struct Racetrack {
-    cells: Vec<Vec<CellKind>>,
+    cells: Grid<CellKind>,
     size: Pos,
     start: Pos,
     end: Pos,
 }
 
 impl Racetrack {
-    fn parse(input: Lines) -> Self {
+    fn parse(input: Lines) -> Result<Self, AocError> {
         let mut start = None;
         let mut end = None;
-        let mut cells = input
-            .enumerate()
-            .map(|(y, line)| {
-                line.chars()
-                    .enumerate()
-                    .map(|(x, c)| {
-                        match c {
-                            'S' => {
-                                start = Some(Pos{x: x as u8, y: y as u8});
-                                CellKind::Track
-                            },
-                            'E' => {
-                                end = Some(Pos{x: x as u8, y: y as u8});
-                                CellKind::Track
-                            },
-                            '.' => CellKind::Track,
-                            '#' => CellKind::Wall,
-                            u => panic!("unexpected char {u}")                            
-                        }
-                    })
-                    .collect_vec()
-            })
-            .collect_vec();
-        let size = Pos{x: cells[0].len() as u8, y: cells.len() as u8};
+        let mut cells = Grid::parse_with_position(input, |pos, c| {
+            match c {
+                'S' => {
+                    start = Some(pos);
+                    Some(CellKind::Track)
+                },
+                'E' => {
+                    end = Some(pos);
+                    Some(CellKind::Track)
+                },
+                '.' => Some(CellKind::Track),
+                '#' => Some(CellKind::Wall),
+                _ => None
+            }
+        })?;
+        let size: Pos = cells.size();
 
         // overwrite all border cells with a different kind to make it easier
         // to know not to visit them without checking coordinates
-        cells.first_mut().unwrap().fill(CellKind::Border);
-        cells.last_mut().unwrap().fill(CellKind::Border);
-        cells.iter_mut().for_each(|row| {
-            *row.first_mut().unwrap() = CellKind::Border;
-            *row.last_mut().unwrap() = CellKind::Border;
-        });
-
-        Self{cells, size, start: start.expect("start"), end: end.expect("end")}
+        let border = cells.positions()
+            .filter(|pos: &Pos| pos.x == 0 || pos.y == 0 || pos.x == size.x - 1 || pos.y == size.y - 1)
+            .collect_vec();
+        for pos in border {
+            cells[pos] = CellKind::Border;
+        }
+
+        let start = start.ok_or_else(|| AocError::new("didn't find start"))?;
+        let end = end.ok_or_else(|| AocError::new("didn't find end"))?;
+        Ok(Self{cells, size, start, end})
     }
     
     fn get_cell(&self, pos: Pos) -> CellKind {
-        self.cells[pos.y as usize][pos.x as usize]
+        self.cells[pos]
     }
 
     fn neighbors(&self, pos: Pos) -> [(Pos, CellKind); 4] {
-        [
-            Pos{x: pos.x + 1, y: pos.y},
-            Pos{x: pos.x, y: pos.y - 1},
-            Pos{x: pos.x - 1, y: pos.y},
-            Pos{x: pos.x, y: pos.y + 1},
-        ].map(|neighbor| (neighbor, self.get_cell(neighbor)))
+        // the border keeps every track position's neighbors inside the racetrack
+        Direction4::ALL.map(|dir| {
+            let neighbor = pos.wrapping_add(dir.offset());
+            (neighbor, self.get_cell(neighbor))
+        })
     }
 
     fn cells_within_distance(&self, center: Pos, max_distance: u8) -> Vec<Pos> {
@@ -84,54 +83,27 @@ impl Racetrack {
     }
  }
 
-struct TimeSolver<'a> {
-    racetrack: &'a Racetrack,
-    times: Vec<Vec<Option<usize>>>,
+struct TimeSolver {
+    times: Grid<Option<usize>>,
 }
 
-impl<'a> TimeSolver<'a> {
-    fn new(racetrack: &'a Racetrack) -> Self {
-        let times = repeat_n(
-            repeat_n(None, racetrack.size.x as usize)
-                .collect_vec(),
-            racetrack.size.y as usize
-        )
-        .collect_vec();
-        Self{racetrack, times}
-    }
-
-    fn solve(racetrack: &'a Racetrack, start: Pos) -> Self {
-        let mut solver = Self::new(racetrack);
-        solver.find_shortest_times(start);
-        solver
+impl TimeSolver {
+    fn solve(racetrack: &Racetrack, start: Pos) -> Self {
+        Self{times: Self::find_shortest_times(racetrack, start)}
     }
 
     fn get_time(&self, pos: Pos) -> Option<usize> {
-        self.times[pos.y as usize][pos.x as usize]
+        self.times[pos]
     }
 
-    fn replace_if_better(&mut self, pos: Pos, time: usize) -> bool {
-        let existing = &mut self.times[pos.y as usize][pos.x as usize];
-        if existing.as_ref().is_none_or(|old| time < *old) {
-            *existing = Some(time);
-            true
-        } else {
-            false
-        }
-    }
-
-    fn find_shortest_times(&mut self, start: Pos) {
-        let mut traverse = VecDeque::new();
-        traverse.push_back((start, 0));
-        while let Some((pos, time)) = traverse.pop_front() {
-            if self.replace_if_better(pos, time) {
-                for (npos, kind) in self.racetrack.neighbors(pos) {
-                    if kind == CellKind::Track {
-                        traverse.push_back((npos, time + 1));
-                    }
-                }
-            }
-        }
+    fn find_shortest_times(racetrack: &Racetrack, start: Pos) -> Grid<Option<usize>> {
+        let times = Grid::new(racetrack.cells.width(), racetrack.cells.height(), None);
+        bfs_costs(start, times, |&pos| {
+            racetrack.neighbors(pos)
+                .into_iter()
+                .filter(|&(_, kind)| kind == CellKind::Track)
+                .map(|(npos, _)| npos)
+        })
     }
 }
 
@@ -145,18 +117,15 @@ fn get_cheat_histogram(racetrack: Racetrack, max_cheat: u8, threshold: usize) ->
     let from_end = TimeSolver::solve(&racetrack, racetrack.end);
     let best_time_without_cheat = from_end.get_time(racetrack.start)
         .expect("time from start to end");
-    for y in 0..racetrack.size.y {
-        for x in 0..racetrack.size.x {
-            let start_pos = Pos{x, y};
-            if let Some(start_time) = from_start.get_time(start_pos) {
-                for cheat_end_pos in racetrack.cells_within_distance(start_pos, max_cheat) {
-                    if let Some(end_time) = from_end.get_time(cheat_end_pos) {
-                        let cheat_time = manhattan_distance(start_pos, cheat_end_pos) as usize;
-                        let time_with_cheat = cheat_time + start_time + end_time;
-                        let saved = best_time_without_cheat.saturating_sub(time_with_cheat);
-                        if saved >= threshold && saved > 0 {
-                            *histogram.entry(saved).or_default() += 1;
-                        }
+    for start_pos in racetrack.cells.positions() {
+        if let Some(start_time) = from_start.get_time(start_pos) {
+            for cheat_end_pos in racetrack.cells_within_distance(start_pos, max_cheat) {
+                if let Some(end_time) = from_end.get_time(cheat_end_pos) {
+                    let cheat_time = manhattan_distance(start_pos, cheat_end_pos) as usize;
+                    let time_with_cheat = cheat_time + start_time + end_time;
+                    let saved = best_time_without_cheat.saturating_sub(time_with_cheat);
+                    if saved >= threshold && saved > 0 {
+                        *histogram.entry(saved).or_default() += 1;
                     }
                 }
             }
@@ -165,37 +134,58 @@ fn get_cheat_histogram(racetrack: Racetrack, max_cheat: u8, threshold: usize) ->
     histogram
 }
 
-fn part1(input: Lines) -> String {
-    let racetrack = Racetrack::parse(input);
-    get_cheat_histogram(racetrack, 2, 100)
+/**
+ * Only cheats saving at least `threshold` picoseconds are counted.
+ */
+const PARAMS: &[(&str, &str)] = &[("threshold", "100")];
+
+fn count_cheats(input: Lines, max_cheat: u8, params: &Params) -> Result<usize, AocError> {
+    let racetrack = Racetrack::parse(input)?;
+    Ok(get_cheat_histogram(racetrack, max_cheat, params.get("threshold")?)
         .into_values()
-        .sum::<usize>()
-        .to_string()
+        .sum::<usize>())
 }
 
-fn part2(input: Lines) -> String {
-    let racetrack = Racetrack::parse(input);
-    get_cheat_histogram(racetrack, 20, 100)
-        .into_values()
-        .sum::<usize>()
-        .to_string()
+fn part1(input: Lines, params: &Params) -> Result<usize, AocError> {
+    count_cheats(input, 2, params)
+}
+
+fn part2(input: Lines, params: &Params) -> Result<usize, AocError> {
+    count_cheats(input, 20, params)
+}
+
+pub fn day() -> Day {
+    create_day!()
+        .input(include_str!("input.txt"))
+        .answers(include_str!("answers.toml"))
+        .constraint(Constraint::grid_at_most(MAX_SIZE, MAX_SIZE, "U8Vec2"))
+        .part_with_params(named!(part1), PARAMS)
+        .part_with_params(named!(part2), PARAMS)
 }
 
-fn main() {
-    let input = include_str!("input.txt");
-    let runner: &Runner = create_runner!();
-    runner.run(named!(part1), input);
-    runner.run(named!(part2), input);
+fn main() -> ExitCode {
+    Runner::create().run(&day())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use advent_of_code::verify;
+    use advent_of_code::aoc_examples;
+    use itertools::repeat_n;
+
+    #[test]
+    fn constraints() {
+        assert_eq!(day().validate(include_str!("input.txt")), Ok(()));
+        let too_large = repeat_n("#".repeat(300), 300).join("\n");
+        assert_eq!(
+            day().validate(&too_large).unwrap_err().message,
+            "grid 300x300 exceeds U8Vec2 (at most 237x237)"
+        );
+    }
 
     #[test]
     fn test_get_cheat_histogram_part1() {
-        let racetrack = Racetrack::parse(include_str!("example.txt").lines());
+        let racetrack = Racetrack::parse(include_str!("example.txt").lines()).unwrap();
         assert_eq!(
             get_cheat_histogram(racetrack, 2, 0),
             [
@@ -216,7 +206,7 @@ mod tests {
 
     #[test]
     fn test_get_cheat_histogram_part2() {
-        let racetrack = Racetrack::parse(include_str!("example.txt").lines());
+        let racetrack = Racetrack::parse(include_str!("example.txt").lines()).unwrap();
         assert_eq!(
             get_cheat_histogram(racetrack, 20, 50),
             [
@@ -238,12 +228,10 @@ mod tests {
         );
     }
 
-    #[test]
-    fn example() {
-        let input = include_str!("example.txt");
-        // One of the rare puzzles where this test is pointless.
-        // The example is small enough that no cheat can save 100 picoseconds.
-        verify!(part1, input, "0");
-        verify!(part2, input, "0");
+    // The example is small enough that no cheat can save 100 picoseconds,
+    // so count the ones listed in the puzzle instead.
+    aoc_examples! {
+        saving_12: "example.txt" with Params::create(PARAMS).with("threshold", 12) => { part1: 8 },
+        saving_76: "example.txt" with Params::create(PARAMS).with("threshold", 76) => { part2: 3 },
     }
 }
@@ -1,8 +1,8 @@
-use advent_of_code::{create_runner, named, Named, Runner};
+use advent_of_code::{create_day, named, AocError, Day, Named, Params, Runner};
 use glam::I8Vec2;
 use itertools::Itertools;
 use phf::phf_map;
-use std::{collections::HashMap, iter::{once, repeat_n, RepeatN}, str::Lines};
+use std::{collections::HashMap, iter::{once, repeat_n, RepeatN}, process::ExitCode, str::Lines};
 
 type Pos = I8Vec2;
 
@@ -171,25 +171,38 @@ fn code_complexity(code: &str, directional_robots: usize) -> usize {
     )
 }
 
-fn part1(input: Lines) -> String {
-    input.into_iter()
-        .map(|code| code_complexity(code, 2))
-        .sum::<usize>()
-        .to_string()
+fn total_complexity(input: Lines, params: &Params) -> Result<usize, AocError> {
+    let directional_robots = params.get::<usize>("robots")?;
+    if directional_robots == 0 {
+        return Err(AocError::new("at least one directional robot is needed"));
+    }
+    Ok(input.into_iter()
+        .map(|code| code_complexity(code, directional_robots))
+        .sum::<usize>())
+}
+
+const PART1_PARAMS: &[(&str, &str)] = &[("robots", "2")];
+
+fn part1(input: Lines, params: &Params) -> Result<usize, AocError> {
+    total_complexity(input, params)
 }
 
-fn part2(input: Lines) -> String {
-    input.into_iter()
-        .map(|code| code_complexity(code, 25))
-        .sum::<usize>()
-        .to_string()
+const PART2_PARAMS: &[(&str, &str)] = &[("robots", "25")];
+
+fn part2(input: Lines, params: &Params) -> Result<usize, AocError> {
+    total_complexity(input, params)
+}
+
+pub fn day() -> Day {
+    create_day!()
+        .input(include_str!("input.txt"))
+        .answers(include_str!("answers.toml"))
+        .part_with_params(named!(part1), PART1_PARAMS)
+        .part_with_params(named!(part2), PART2_PARAMS)
 }
 
-fn main() {
-    let input = include_str!("input.txt");
-    let runner: &Runner = create_runner!();
-    runner.run(named!(part1), input);
-    runner.run(named!(part2), input);
+fn main() -> ExitCode {
+    Runner::create().run(&day())
 }
 
 #[cfg(test)]
@@ -197,7 +210,7 @@ mod tests {
     use std::collections::HashSet;
 
     use super::*;
-    use advent_of_code::verify;
+    use advent_of_code::aoc_examples;
     use rstest::rstest;
 
     #[rstest]
@@ -238,9 +251,7 @@ mod tests {
         assert_eq!(code_complexity_sequence(code, sequence_len), sequence_len * numeric)
     }
 
-    #[test]
-    fn example() {
-        let input = include_str!("example.txt");
-        verify!(part1, input, "126384");
+    aoc_examples! {
+        example: "example.txt" with Params::create(PART1_PARAMS) => { part1: 126384 },
     }
 }
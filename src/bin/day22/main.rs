@@ -1,6 +1,6 @@
-use advent_of_code::{create_runner, named, Named, Runner};
+use advent_of_code::{create_day, named, Day, Named, Runner};
 use itertools::{iterate, Itertools};
-use std::{collections::HashMap, str::Lines};
+use std::{collections::HashMap, process::ExitCode, str::Lines};
 
 type Secret = u64;
 
@@ -24,12 +24,11 @@ fn nth_secret_number(num: Secret, nth: usize) -> Secret {
         .expect("nth must exist")
 }
 
-fn part1(input: Lines) -> String {
+fn part1(input: Lines) -> Secret {
     input
         .map(|line| line.parse::<Secret>().expect("numeric"))
         .map(|n| nth_secret_number(n, 2000))
         .sum::<Secret>()
-        .to_string()
 }
 
 fn buyer_sequences(secret: Secret, count: usize) -> HashMap<[i8; 4], usize> {
@@ -44,7 +43,7 @@ fn buyer_sequences(secret: Secret, count: usize) -> HashMap<[i8; 4], usize> {
         })
 }
 
-fn part2(input: Lines) -> String {
+fn part2(input: Lines) -> usize {
     input
         .map(|line| line.parse::<Secret>().expect("numeric"))
         .map(|n| buyer_sequences(n, 2000))
@@ -55,25 +54,28 @@ fn part2(input: Lines) -> String {
             })
         })
         .unwrap()
-        .values()
-        .into_iter()
+        .into_values()
         .max()
         .unwrap()
-        .to_string()
 }     
 
 
-fn main() {
-    let input = include_str!("input.txt");
-    let runner: &Runner = create_runner!();
-    runner.run(named!(part1), input);
-    runner.run(named!(part2), input);
+pub fn day() -> Day {
+    create_day!()
+        .input(include_str!("input.txt"))
+        .answers(include_str!("answers.toml"))
+        .part(named!(part1))
+        .part(named!(part2))
+}
+
+fn main() -> ExitCode {
+    Runner::create().run(&day())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use advent_of_code::verify;
+    use advent_of_code::aoc_examples;
     use itertools::Itertools;
 
     #[test]
@@ -105,15 +107,8 @@ mod tests {
         assert_eq!(nth_secret_number(2024, 2000), 8667524);
     }
 
-    #[test]
-    fn example_part1() {
-        let input = include_str!("example.txt");
-        verify!(part1, input, "37327623");
-    }
-
-    #[test]
-    fn example_part2() {
-        let input = "1\n2\n3\n2024";
-        verify!(part2, input, "23");
+    aoc_examples! {
+        example: "example.txt" => { part1: 37327623 },
+        example2: text "1\n2\n3\n2024" => { part2: 23 },
     }
 }
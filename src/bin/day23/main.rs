@@ -1,114 +1,71 @@
-use advent_of_code::{create_runner, named, Named, Runner};
+use advent_of_code::{create_day, graph::Graph, named, Day, Named, Runner};
 use itertools::Itertools;
-use std::{collections::{HashMap, HashSet, VecDeque}, iter::once, str::Lines};
+use std::{process::ExitCode, str::Lines};
 
-type Computer = String;
+type Network<'a> = Graph<&'a str>;
 
-fn parse_connection(line: &str) -> (Computer, Computer) {
-    line.splitn(2, '-')
-        .map(|s| s.to_owned())
-        .collect_tuple()
-        .unwrap()
+fn parse_connection(line: &str) -> (&str, &str) {
+    line.split_once('-').unwrap()
 }
 
-fn parse_connections(input: Lines) -> HashMap<Computer, HashSet<Computer>> {
-    let mut sets: HashMap<Computer, HashSet<Computer>> = Default::default();
-    for (a, b) in input.map(parse_connection) {
-        sets.entry(a.clone()).or_default().insert(b.clone());
-        sets.entry(b.clone()).or_default().insert(a.clone());
-    }
-    sets
+fn parse_connections(input: Lines) -> Network {
+    input.map(parse_connection).collect()
 }
 
-fn find_interconnected(connections: HashMap<Computer, HashSet<Computer>>) -> Vec<[Computer; 3]> {
-    connections.iter()
-        .flat_map(|(a, a_conns)| {
-            a_conns.iter()
-                .filter(|b| *a < **b)
-                .flat_map(|b| {
-                    connections[b].intersection(a_conns)
-                        .filter_map(|c| {
-                            if *b < *c {
-                                Some([a.to_owned(), b.to_owned(), c.to_owned()])
-                            } else {
-                                None
-                            }
-                        })
-                })
-        })
+fn find_interconnected<'a>(network: &Network<'a>) -> Vec<[&'a str; 3]> {
+    network.triangles()
+        .into_iter()
+        .map(|triangle| triangle.map(|id| *network.name(id)))
         .collect_vec()
 }
 
-fn part1(input: Lines) -> String {
-    find_interconnected(parse_connections(input))
+fn part1(input: Lines) -> usize {
+    find_interconnected(&parse_connections(input))
         .into_iter()
         .filter(|set| {
             set.iter().any(|computer| computer.starts_with('t'))
         })
         .count()
-        .to_string()
-}
-
-fn all_connected(connections: &HashMap<Computer, HashSet<Computer>>, set: &HashSet<Computer>) -> HashSet<Computer> {
-    set.iter()
-        .map(|a| connections.get(a).cloned().unwrap_or_default())
-        .reduce(|a, b| {
-            a.intersection(&b).cloned().collect()
-        })
-        .unwrap_or_default()
-}
-
-fn are_connected(connections: &HashMap<Computer, HashSet<Computer>>, a_set: &HashSet<Computer>, b_set: &HashSet<Computer>) -> bool {
-    a_set.is_subset(&all_connected(connections, b_set)) &&
-        b_set.is_subset(&all_connected(connections, a_set))
 }
 
-fn largest_connected_component(connections: HashMap<Computer, HashSet<Computer>>) -> Option<HashSet<Computer>> {
-    let mut largest: Option<HashSet<Computer>> = None;
-    let mut processing: VecDeque<HashSet<Computer>> = connections.keys()
-        .sorted()
-        .map(|c| HashSet::from_iter(once(c.to_owned())))
-        .collect();
-    while let Some(a) = processing.pop_front() {
-        if let Some(b_pos) = processing.iter().position(|b| are_connected(&connections, &a, &b)) {
-            let b= processing.remove(b_pos).unwrap();
-            processing.push_back(a.union(&b)
-                .cloned()
-                .collect());
-        } else {
-            largest = [largest, Some(a)].into_iter().flatten().max_by_key(|c| c.len())
-        }
-    }
-    largest
-}
-
-fn format_largest_component(component: HashSet<Computer>) -> String {
-    component.into_iter()
+fn format_largest_clique(network: &Network) -> String {
+    network.maximum_clique()
+        .into_iter()
+        .map(|id| network.name(id))
         .sorted()
         .join(",")
 }
 
 fn part2(input: Lines) -> String {
-    largest_connected_component(parse_connections(input))
-        .map(format_largest_component)
-        .unwrap_or_default()
+    format_largest_clique(&parse_connections(input))
+}
+
+pub fn day() -> Day {
+    create_day!()
+        .input(include_str!("input.txt"))
+        .answers(include_str!("answers.toml"))
+        .part(named!(part1))
+        .part(named!(part2))
 }
 
-fn main() {
-    let input = include_str!("input.txt");
-    let runner: &Runner = create_runner!();
-    runner.run(named!(part1), input);
-    runner.run(named!(part2), input);
+fn main() -> ExitCode {
+    Runner::create().run(&day())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use advent_of_code::verify;
+    use advent_of_code::aoc_examples;
 
     #[test]
     fn test_find_interconnected() {
-        let mut interconnected = find_interconnected(parse_connections(include_str!("example.txt").lines()));
+        let mut interconnected = find_interconnected(&parse_connections(include_str!("example.txt").lines()))
+            .into_iter()
+            .map(|mut set| {
+                set.sort();
+                set
+            })
+            .collect_vec();
         interconnected.sort();
         assert_eq!(
             interconnected,
@@ -129,10 +86,7 @@ mod tests {
         )
     }
 
-    #[test]
-    fn example() {
-        let input = include_str!("example.txt");
-        verify!(part1, input, "7");
-        verify!(part2, input, "co,de,ka,ta");
+    aoc_examples! {
+        example: "example.txt" => { part1: 7, part2: "co,de,ka,ta" },
     }
 }
@@ -1,6 +1,8 @@
-use advent_of_code::{create_runner, named, Named, Runner};
+use advent_of_code::{constraint::Constraint, create_day, named, AocError, Day, Named, Runner};
 use itertools::{FoldWhile, Itertools};
-use std::{cmp::Ordering, collections::{hash_map::Entry, HashMap, HashSet, VecDeque}, fmt::Display, fs::File, io::Write, str::{FromStr, Lines}};
+use std::{collections::{hash_map::Entry, HashMap}, fmt::Display, fs::File, io::Write, process::ExitCode, str::{FromStr, Lines}};
+#[cfg(test)]
+use std::{cmp::Ordering, collections::{HashSet, VecDeque}};
 
 #[derive(Clone, Debug, PartialEq)]
 enum Operation {
@@ -61,14 +63,35 @@ struct Expression {
 }
 
 impl Expression {
-    fn parse(line: &str) -> (String, Expression) {
-        let (expression, key) = line.split_once(" -> ").unwrap();
-        let (a, op, b) = expression.split_ascii_whitespace().collect_tuple().unwrap();
+    fn parse(line: &str) -> Result<(String, Expression), AocError> {
+        let format_error = || AocError::new(format!("expected `a OP b -> c` but found {line}"));
+        let (expression, key) = line.split_once(" -> ").ok_or_else(format_error)?;
+        let (a, op, b) = expression.split_ascii_whitespace().collect_tuple().ok_or_else(format_error)?;
         let a = a.to_owned();
         let b = b.to_owned();
-        let op = op.parse::<Operation>().unwrap();
-        (key.to_owned(), Self{a, b, op})
+        let op = op.parse::<Operation>().map_err(|_| AocError::new(format!("unknown operation {op}")))?;
+        Ok((key.to_owned(), Self{a, b, op}))
+    }
+}
+
+/**
+ * How many bits of a number `get_number` reads, which must all be wires.
+ */
+const NUMBER_BITS: usize = 63;
+
+/**
+ * Output bits past `NUMBER_BITS` would silently be left out of the number.
+ */
+fn output_bits_fit(input: &str) -> Result<(), AocError> {
+    for (line_index, line) in input.lines().enumerate() {
+        if let Some((_, wire)) = line.split_once(" -> ") {
+            if wire.strip_prefix('z').and_then(|bit| bit.parse::<usize>().ok()).is_some_and(|bit| bit >= NUMBER_BITS) {
+                return Err(AocError::new(format!("wire {wire} exceeds the {NUMBER_BITS} bits of get_number"))
+                    .at(line_index + 1, line.len() - wire.len() + 1));
+            }
+        }
     }
+    Ok(())
 }
 
 struct Input {
@@ -79,22 +102,25 @@ struct Input {
 }
 
 impl Input {
-    fn parse_value(line: &str) -> (String, bool) {
-        let (a, b) = line.split_once(": ").unwrap();
-        (a.to_owned(), b.parse::<u8>().unwrap() != 0)
+    fn parse_value(line: &str) -> Result<(String, bool), AocError> {
+        let (a, b) = line.split_once(": ")
+            .ok_or_else(|| AocError::new(format!("expected `wire: value` but found {line}")))?;
+        let value = b.parse::<u8>().map_err(|e| AocError::new(format!("wire value {e}")))?;
+        Ok((a.to_owned(), value != 0))
     }
 
-    fn parse(mut input: Lines) -> Self {
-        let initial_values = input.by_ref()
-            .take_while(|line| !line.is_empty())
-            .map(Self::parse_value)
-            .collect();
+    fn parse(input: Lines) -> Result<Self, AocError> {
+        let mut lines = input.enumerate().map(|(index, line)| (index + 1, line));
+        let initial_values = lines.by_ref()
+            .take_while(|(_, line)| !line.is_empty())
+            .map(|(line_number, line)| Self::parse_value(line).map_err(|e| e.at_line(line_number)))
+            .collect::<Result<_, _>>()?;
         let cached_values = HashMap::new();
-        let expressions = input
-            .map(Expression::parse)
-            .collect();
+        let expressions = lines
+            .map(|(line_number, line)| Expression::parse(line).map_err(|e| e.at_line(line_number)))
+            .collect::<Result<_, _>>()?;
         let output_mapping = HashMap::new();
-        Self{initial_values, cached_values, expressions, output_mapping}
+        Ok(Self{initial_values, cached_values, expressions, output_mapping})
     }
 
     fn eval_expression(&mut self, expression: &Expression) -> Option<bool> {
@@ -120,6 +146,7 @@ impl Input {
             })
     }
 
+    #[cfg(test)]
     fn connected_gates(&self, key: &str) -> HashSet<String> {
         let mut gates = HashSet::new();
         let mut traverse = VecDeque::new();
@@ -139,14 +166,14 @@ impl Input {
     fn swap_outputs(&mut self, swap: &[&str]) {
         self.cached_values.clear();
         self.output_mapping.clear();
-        for (&a, &b) in swap.into_iter().tuples() {
+        for (&a, &b) in swap.iter().tuples() {
             self.output_mapping.insert(a.to_owned(), b.to_owned());
             self.output_mapping.insert(b.to_owned(), a.to_owned());
         }
     }
 
     fn get_number(&mut self, prefix: &str) -> u64 {
-        (0..63)
+        (0..NUMBER_BITS)
             .fold_while(0, |num, i| {
                 match self.eval_wire(&format!("{prefix}{i:02}")) {
                     Some(true) => FoldWhile::Continue(num | (1<<i)),
@@ -182,8 +209,8 @@ impl Input {
                 ("label", &label),
             ];
             let mut edge_color = "black";
-            if output.starts_with('z') {
-                let bit = output[1..].parse::<usize>().unwrap();
+            if let Some(bit) = output.strip_prefix('z') {
+                let bit = bit.parse::<usize>().unwrap();
                 attributes.push(("style", "filled"));
                 if (bad_output_bits & (1 << bit)) == 0 {
                     attributes.push(("fillcolor", "green"));
@@ -209,11 +236,12 @@ impl Input {
     }
 }
 
-fn part1(input: Lines) -> String {
-    let mut input = Input::parse(input);
-    input.get_number("z").to_string()
+fn part1(input: Lines) -> Result<u64, AocError> {
+    let mut input = Input::parse(input)?;
+    Ok(input.get_number("z"))
 }
 
+#[cfg(test)]
 fn bit_wires(num: u64, prefix: &str) -> Vec<String> {
     (0..u64::BITS)
         .filter(|b| num & (1 << b) != 0)
@@ -261,6 +289,7 @@ fn test_and(input: &mut Input) -> u64 {
         .unwrap()
 }
 
+#[cfg(test)]
 fn maybe_replace_wires_with_upstream(input: &mut Input, bad_wires: &[String], swapped_pairs: usize, tester: &impl Fn(&mut Input) -> u64) -> Option<Vec<String>> {
     println!("upstream[{len}]={joined}", len=bad_wires.len(), joined=format_wires(bad_wires));
     bad_wires.iter()
@@ -284,6 +313,7 @@ fn maybe_replace_wires_with_upstream(input: &mut Input, bad_wires: &[String], sw
     .next()
 }
 
+#[cfg(test)]
 /**
  * Checking all permutations of 8 wires = 8! = 40320.
  * Number of pairings is 8! / (2^4 * 4!) = 104, but writing an algorithm to return only these pairings is harder.
@@ -291,7 +321,7 @@ fn maybe_replace_wires_with_upstream(input: &mut Input, bad_wires: &[String], sw
  */
 fn check_wire_swaps(input: &mut Input, bad_wires: &[String], swapped_pairs: usize, tester: &impl Fn(&mut Input) -> u64) -> Option<Vec<String>> {
     println!("   swaps[{len}]={joined}", len=bad_wires.len(), joined=format_wires(bad_wires));
-    bad_wires.into_iter()
+    bad_wires.iter()
         .combinations(swapped_pairs)
         .flat_map(|firsts| {
             let others = bad_wires.iter()
@@ -299,7 +329,7 @@ fn check_wire_swaps(input: &mut Input, bad_wires: &[String], swapped_pairs: usiz
                 .collect_vec();
             others.into_iter()
                 .permutations(swapped_pairs)
-                .map(move |others| firsts.iter().cloned().interleave_shortest(others.into_iter()).map(|s| s as &str).collect_vec())
+                .map(move |others| firsts.iter().cloned().interleave_shortest(others).map(|s| s as &str).collect_vec())
         })
         .find(|swap| {
             input.swap_outputs(swap);
@@ -308,6 +338,7 @@ fn check_wire_swaps(input: &mut Input, bad_wires: &[String], swapped_pairs: usiz
         .map(|result| result.into_iter().map(|s| s.to_owned()).collect_vec())
 }
 
+#[cfg(test)]
 fn find_swapped_wires_internal(input: &mut Input, bad_wires: &[String], swapped_pairs: usize, tester: &impl Fn(&mut Input) -> u64) -> Option<Vec<String>> {
     match bad_wires.len().cmp(&(swapped_pairs * 2)) {
         Ordering::Less => None,
@@ -318,62 +349,80 @@ fn find_swapped_wires_internal(input: &mut Input, bad_wires: &[String], swapped_
 }
 
 fn format_wires(wires: &[String]) -> String {
-    wires.into_iter().sorted().join(",")
+    wires.iter().sorted().join(",")
 }
 
+#[cfg(test)]
 fn find_swapped_wires(input: Lines, swapped_pairs: usize, tester: &impl Fn(&mut Input) -> u64) -> Option<String> {
-    let mut input = Input::parse(input);
+    let mut input = Input::parse(input).expect("valid input");
     let bad_bits = tester(&mut input);
     let bad_wires = bit_wires(bad_bits, "z");
     find_swapped_wires_internal(&mut input, &bad_wires, swapped_pairs, tester)
         .map(|wires| format_wires(&wires))
 }
 
-fn part2(input: Lines) -> String {
-    let mut input = Input::parse(input);
+fn part2(input: Lines) -> Result<String, AocError> {
+    let mut input = Input::parse(input)?;
     let swap_wires = vec!["gjc", "qjj", "z17", "wmp", "z26", "gvm", "z39", "qsb"];
     input.swap_outputs(&swap_wires);
     let bad_output_bits = test_sum(&mut input);
-    let mut out = File::create("gates.dot").unwrap();
-    input.write_dot(bad_output_bits, &mut out).unwrap();
-    format_wires(&swap_wires.into_iter().map(|s| s.to_owned()).collect_vec())
+    File::create("gates.dot")
+        .and_then(|mut out| input.write_dot(bad_output_bits, &mut out))
+        .map_err(|e| AocError::new(format!("failed to write gates.dot: {e}")))?;
+    Ok(format_wires(&swap_wires.into_iter().map(|s| s.to_owned()).collect_vec()))
 }
 
-fn main() {
-    let input = include_str!("input.txt");
-    let runner: &Runner = create_runner!();
-    runner.run(named!(part1), input);
-    runner.run(named!(part2), input);
+pub fn day() -> Day {
+    create_day!()
+        .input(include_str!("input.txt"))
+        .answers(include_str!("answers.toml"))
+        .constraint(Constraint::create(output_bits_fit))
+        .part(named!(part1))
+        .part(named!(part2))
+}
+
+fn main() -> ExitCode {
+    Runner::create().run(&day())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use advent_of_code::verify;
+    use advent_of_code::aoc_examples;
+
+    #[test]
+    fn constraints() {
+        assert_eq!(day().validate(include_str!("input.txt")), Ok(()));
+        assert_eq!(
+            day().validate("x00: 1\n\nx00 AND x00 -> z63").unwrap_err().to_string(),
+            "line 3, column 16: wire z63 exceeds the 63 bits of get_number"
+        );
+    }
 
     #[test]
     fn parse_value() {
-        assert_eq!(Input::parse_value("x00: 1"), ("x00".to_owned(), true));
+        assert_eq!(Input::parse_value("x00: 1"), Ok(("x00".to_owned(), true)));
     }
 
     #[test]
     fn parse_expression() {
         assert_eq!(
             Expression::parse("x00 AND y00 -> z00"),
-            ("z00".to_owned(), Expression{a: "x00".to_owned(), b: "y00".to_owned(), op: Operation::And})
+            Ok(("z00".to_owned(), Expression{a: "x00".to_owned(), b: "y00".to_owned(), op: Operation::And}))
         );
     }
 
     #[test]
-    fn part1_example1() {
-        let input = include_str!("example.txt");
-        verify!(part1, input, "4");
+    fn parse_error() {
+        assert_eq!(
+            part1("x00: 1\ny00: 0\n\nx00 NAND y00 -> z00".lines()),
+            Err(AocError::new("unknown operation NAND").at_line(4))
+        );
     }
 
-    #[test]
-    fn part1_example2() {
-        let input = include_str!("example2.txt");
-        verify!(part1, input, "2024"); 
+    aoc_examples! {
+        example1: "example.txt" => { part1: 4 },
+        example2: "example2.txt" => { part1: 2024 },
     }
 
     #[test]
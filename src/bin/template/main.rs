@@ -1,30 +1,32 @@
-use advent_of_code::{create_runner, named, Named, Runner};
-use std::str::Lines;
+use advent_of_code::{create_day, named, Day, Named, Runner};
+use std::{process::ExitCode, str::Lines};
 
-fn part1(input: Lines) -> String {
-    input.take(0).count().to_string()
+fn part1(input: Lines) -> usize {
+    input.take(0).count()
 }
 
-fn part2(input: Lines) -> String {
-    input.take(0).count().to_string()
+fn part2(input: Lines) -> usize {
+    input.take(0).count()
 }
 
-fn main() {
-    let input = include_str!("input.txt");
-    let runner: &Runner = create_runner!();
-    runner.run(named!(part1), input);
-    runner.run(named!(part2), input);
+pub fn day() -> Day {
+    create_day!()
+        .input(include_str!("input.txt"))
+        .answers(include_str!("answers.toml"))
+        .part(named!(part1))
+        .part(named!(part2))
+}
+
+fn main() -> ExitCode {
+    Runner::create().run(&day())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use advent_of_code::verify;
+    use advent_of_code::aoc_examples;
 
-    #[test]
-    fn example() {
-        let input = include_str!("example.txt");
-        verify!(part1, input, "0");
-        verify!(part2, input, "0");
+    aoc_examples! {
+        example: "example.txt" => { part1: 0, part2: 0 },
     }
 }
@@ -0,0 +1,304 @@
+use std::fmt;
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/**
+ * Where a [`BitSet`] keeps its words of bits, either on the heap so it grows
+ * as bits are inserted, or in a fixed array of `N` words.
+ */
+pub trait Words: Clone {
+    fn empty() -> Self;
+
+    fn words(&self) -> &[u64];
+
+    /**
+     * The words, with at least `len` of them, or `None` when they can't
+     * grow that far.
+     */
+    fn words_mut(&mut self, len: usize) -> Option<&mut [u64]>;
+}
+
+impl Words for Vec<u64> {
+    fn empty() -> Self {
+        Vec::new()
+    }
+
+    fn words(&self) -> &[u64] {
+        self
+    }
+
+    fn words_mut(&mut self, len: usize) -> Option<&mut [u64]> {
+        if self.len() < len {
+            self.resize(len, 0);
+        }
+        Some(self)
+    }
+}
+
+impl<const N: usize> Words for [u64; N] {
+    fn empty() -> Self {
+        [0; N]
+    }
+
+    fn words(&self) -> &[u64] {
+        self
+    }
+
+    fn words_mut(&mut self, len: usize) -> Option<&mut [u64]> {
+        (len <= N).then_some(self)
+    }
+}
+
+/**
+ * A set of small numbers as one bit each, which makes combining sets cheap.
+ * It grows on the heap as bits are inserted, or a [`FixedBitSet`] holds a
+ * fixed number of bits without allocating:
+ *
+ * ```
+ * use advent_of_code::bitset::{BitSet, FixedBitSet};
+ *
+ * let a: BitSet = [1, 5, 200].into_iter().collect();
+ * let b: FixedBitSet<2> = [5, 64].into_iter().collect();
+ * let b: BitSet = b.iter().collect();
+ * assert_eq!(a.union(&b).iter().collect::<Vec<_>>(), [1, 5, 64, 200]);
+ * assert_eq!(a.difference(&b).len(), 2);
+ * assert_eq!(a.last(), Some(200));
+ * ```
+ */
+#[derive(Clone)]
+pub struct BitSet<W: Words = Vec<u64>> {
+    words: W,
+}
+
+/**
+ * A [`BitSet`] of `N` words, holding bits below `64 * N`.
+ */
+pub type FixedBitSet<const N: usize> = BitSet<[u64; N]>;
+
+impl<W: Words> BitSet<W> {
+    pub fn new() -> Self {
+        Self { words: W::empty() }
+    }
+
+    fn word(&self, index: usize) -> u64 {
+        self.words.words().get(index).copied().unwrap_or(0)
+    }
+
+    /**
+     * Adds `bit`, returning whether it's new. Panics when a fixed set is too
+     * small for it.
+     */
+    pub fn insert(&mut self, bit: usize) -> bool {
+        let (index, mask) = (bit / WORD_BITS, 1 << (bit % WORD_BITS));
+        let capacity = self.words.words().len() * WORD_BITS;
+        let word = &mut self
+            .words
+            .words_mut(index + 1)
+            .unwrap_or_else(|| panic!("bit {bit} doesn't fit in a set of {capacity} bits"))[index];
+        let new = *word & mask == 0;
+        *word |= mask;
+        new
+    }
+
+    /**
+     * Removes `bit`, returning whether it was in the set.
+     */
+    pub fn remove(&mut self, bit: usize) -> bool {
+        let (index, mask) = (bit / WORD_BITS, 1 << (bit % WORD_BITS));
+        let contained = self.contains(bit);
+        if contained {
+            self.words.words_mut(index + 1).unwrap()[index] &= !mask;
+        }
+        contained
+    }
+
+    pub fn contains(&self, bit: usize) -> bool {
+        self.word(bit / WORD_BITS) & (1 << (bit % WORD_BITS)) != 0
+    }
+
+    /**
+     * How many bits are in the set.
+     */
+    pub fn len(&self) -> usize {
+        self.words
+            .words()
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.words().iter().all(|&word| word == 0)
+    }
+
+    /**
+     * The bits in increasing order.
+     */
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words
+            .words()
+            .iter()
+            .enumerate()
+            .flat_map(|(index, &word)| {
+                let mut word = word;
+                std::iter::from_fn(move || {
+                    (word != 0).then(|| {
+                        let bit = word.trailing_zeros() as usize;
+                        word &= word - 1;
+                        index * WORD_BITS + bit
+                    })
+                })
+            })
+    }
+
+    pub fn first(&self) -> Option<usize> {
+        self.iter().next()
+    }
+
+    pub fn last(&self) -> Option<usize> {
+        let words = self.words.words();
+        let index = words.iter().rposition(|&word| word != 0)?;
+        Some(index * WORD_BITS + (WORD_BITS - 1 - words[index].leading_zeros() as usize))
+    }
+
+    /**
+     * Combines the words of both sets with `f`, as far as `len` words.
+     */
+    fn combine(&self, other: &Self, len: usize, f: impl Fn(u64, u64) -> u64) -> Self {
+        let mut combined = Self::new();
+        if let Some(words) = combined.words.words_mut(len) {
+            for (index, word) in words.iter_mut().enumerate().take(len) {
+                *word = f(self.word(index), other.word(index));
+            }
+        }
+        combined
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let len = self.words.words().len().max(other.words.words().len());
+        self.combine(other, len, |a, b| a | b)
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let len = self.words.words().len().min(other.words.words().len());
+        self.combine(other, len, |a, b| a & b)
+    }
+
+    /**
+     * The bits of this set which aren't in `other`.
+     */
+    pub fn difference(&self, other: &Self) -> Self {
+        self.combine(other, self.words.words().len(), |a, b| a & !b)
+    }
+
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.difference(other).is_empty()
+    }
+
+    pub fn is_superset(&self, other: &Self) -> bool {
+        other.is_subset(self)
+    }
+}
+
+impl<W: Words> Default for BitSet<W> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/**
+ * Sets are equal when they have the same bits, however many words hold them.
+ */
+impl<W: Words> PartialEq for BitSet<W> {
+    fn eq(&self, other: &Self) -> bool {
+        let len = self.words.words().len().max(other.words.words().len());
+        (0..len).all(|index| self.word(index) == other.word(index))
+    }
+}
+
+impl<W: Words> Eq for BitSet<W> {}
+
+impl<W: Words> fmt::Debug for BitSet<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<W: Words> Extend<usize> for BitSet<W> {
+    fn extend<I: IntoIterator<Item = usize>>(&mut self, bits: I) {
+        for bit in bits {
+            self.insert(bit);
+        }
+    }
+}
+
+impl<W: Words> FromIterator<usize> for BitSet<W> {
+    fn from_iter<I: IntoIterator<Item = usize>>(bits: I) -> Self {
+        let mut set = Self::new();
+        set.extend(bits);
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bits<W: Words>(set: &BitSet<W>) -> Vec<usize> {
+        set.iter().collect()
+    }
+
+    #[test]
+    fn insert_remove() {
+        let mut set = BitSet::<Vec<u64>>::new();
+        assert!(set.is_empty());
+        assert!(set.insert(130));
+        assert!(!set.insert(130));
+        assert!(set.insert(0));
+        assert!(set.contains(130) && set.contains(0) && !set.contains(64) && !set.contains(10_000));
+        assert_eq!(
+            (set.len(), set.first(), set.last()),
+            (2, Some(0), Some(130))
+        );
+        assert!(set.remove(130));
+        assert!(!set.remove(130));
+        assert!(!set.remove(10_000));
+        assert_eq!(bits(&set), [0]);
+        assert_eq!(format!("{set:?}"), "{0}");
+        let empty = FixedBitSet::<1>::new();
+        assert_eq!((empty.first(), empty.last()), (None, None));
+    }
+
+    #[test]
+    fn combine() {
+        let a = [1, 63, 64, 300].into_iter().collect::<BitSet>();
+        let b = [1, 64, 65].into_iter().collect::<BitSet>();
+        assert_eq!(bits(&a.union(&b)), [1, 63, 64, 65, 300]);
+        assert_eq!(bits(&a.intersection(&b)), [1, 64]);
+        assert_eq!(bits(&a.difference(&b)), [63, 300]);
+        assert_eq!(bits(&b.difference(&a)), [65]);
+        assert!(a.intersection(&b).is_subset(&a));
+        assert!(a.is_superset(&a.intersection(&b)));
+        assert!(!a.is_subset(&b));
+        // the same bits, though one has more words
+        assert_eq!(
+            a.difference(&[300].into_iter().collect()),
+            [1, 63, 64].into_iter().collect()
+        );
+    }
+
+    #[test]
+    fn fixed() {
+        let a = [0, 127].into_iter().collect::<FixedBitSet<2>>();
+        let b = [127, 5].into_iter().collect::<FixedBitSet<2>>();
+        assert_eq!(bits(&a.union(&b)), [0, 5, 127]);
+        assert_eq!(bits(&a.intersection(&b)), [127]);
+        assert_eq!(a.last(), Some(127));
+    }
+
+    #[test]
+    #[should_panic(expected = "bit 128 doesn't fit in a set of 128 bits")]
+    fn fixed_overflow() {
+        FixedBitSet::<2>::new().insert(128);
+    }
+}
@@ -0,0 +1,155 @@
+use crate::AocError;
+
+/**
+ * A limit on the puzzle input which a day's solution depends on, such as the
+ * largest number which fits the type it's parsed into.
+ *
+ * The runner checks each input against the day's constraints before running
+ * any part, so that input beyond a limit is reported as such instead of
+ * wrapping around or panicking deep inside a part:
+ *
+ * ```
+ * use advent_of_code::constraint::Constraint;
+ *
+ * let constraint = Constraint::grid_at_most(255, 255, "U8Vec2");
+ * let error = constraint.check(&"#".repeat(300)).unwrap_err();
+ * assert_eq!(error.message, "grid 300x1 exceeds U8Vec2 (at most 255x255)");
+ * ```
+ */
+pub struct Constraint {
+    check: Check,
+}
+
+type Check = Box<dyn Fn(&str) -> Result<(), AocError> + Send + Sync>;
+
+impl Constraint {
+    /**
+     * A constraint which doesn't fit any of the others, checked by a function.
+     */
+    pub fn create(check: impl Fn(&str) -> Result<(), AocError> + Send + Sync + 'static) -> Self {
+        Self {
+            check: Box::new(check),
+        }
+    }
+
+    /**
+     * Every number in the input is between 0 and `max`, which is what fits in
+     * `limit`, usually the name of a type.
+     */
+    pub fn numbers_at_most(max: u64, limit: &'static str) -> Self {
+        Self::numbers_within(0, max.into(), limit)
+    }
+
+    /**
+     * Every number in the input, including negative ones, is between `min`
+     * and `max`, which is what fits in `limit`.
+     */
+    pub fn numbers_within(min: i128, max: i128, limit: &'static str) -> Self {
+        Self::create(move |input| {
+            for (line_index, line) in input.lines().enumerate() {
+                for (column, number) in numbers(line) {
+                    let message = match number.parse::<i128>() {
+                        Ok(n) if n < min => {
+                            format!("number {number} is below {limit} (at least {min})")
+                        }
+                        Ok(n) if n <= max => continue,
+                        _ => format!("number {number} exceeds {limit} (at most {max})"),
+                    };
+                    return Err(AocError::new(message).at(line_index + 1, column + 1));
+                }
+            }
+            Ok(())
+        })
+    }
+
+    /**
+     * The input is a grid of characters no larger than `max_width` by
+     * `max_height`, which is what fits in `limit`.
+     */
+    pub fn grid_at_most(max_width: usize, max_height: usize, limit: &'static str) -> Self {
+        Self::create(move |input| {
+            let width = input.lines().map(|line| line.chars().count()).max();
+            let height = input.lines().count();
+            match width {
+                Some(width) if width > max_width || height > max_height => {
+                    Err(AocError::new(format!(
+                        "grid {width}x{height} exceeds {limit} (at most {max_width}x{max_height})"
+                    )))
+                }
+                _ => Ok(()),
+            }
+        })
+    }
+
+    pub fn check(&self, input: &str) -> Result<(), AocError> {
+        (self.check)(input)
+    }
+}
+
+/**
+ * The numbers in `line`, with the 0-based column each starts at. A minus sign
+ * right before one is part of it, unless it follows another number, as in a
+ * range like `1-3`.
+ */
+fn numbers(line: &str) -> impl Iterator<Item = (usize, &str)> {
+    let bytes = line.as_bytes();
+    let is_digit = |index: usize| bytes.get(index).is_some_and(u8::is_ascii_digit);
+    let mut index = 0;
+    std::iter::from_fn(move || {
+        while index < bytes.len() {
+            let start = index;
+            let negative =
+                bytes[start] == b'-' && is_digit(start + 1) && (start == 0 || !is_digit(start - 1));
+            index += 1;
+            if negative || is_digit(start) {
+                while is_digit(index) {
+                    index += 1;
+                }
+                return Some((start, &line[start..index]));
+            }
+        }
+        None
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbers_at_most() {
+        let constraint = Constraint::numbers_at_most(127, "i8");
+        assert_eq!(constraint.check("5,127\n0,12"), Ok(()));
+        assert_eq!(
+            constraint.check("5,127\n0,128").unwrap_err().to_string(),
+            "line 2, column 3: number 128 exceeds i8 (at most 127)"
+        );
+        assert!(constraint.check("99999999999999999999999").is_err());
+        assert_eq!(
+            constraint.check("1,1\n-5,3").unwrap_err().to_string(),
+            "line 2, column 1: number -5 is below i8 (at least 0)"
+        );
+        assert_eq!(constraint.check("1-3,4"), Ok(()));
+    }
+
+    #[test]
+    fn numbers_within() {
+        let constraint = Constraint::numbers_within(-128, 127, "i8");
+        assert_eq!(constraint.check("p=0,4 v=3,-128"), Ok(()));
+        assert_eq!(
+            constraint.check("v=-129").unwrap_err().message,
+            "number -129 is below i8 (at least -128)"
+        );
+    }
+
+    #[test]
+    fn grid_at_most() {
+        let constraint = Constraint::grid_at_most(3, 2, "U8Vec2");
+        assert_eq!(constraint.check("..#\n#.."), Ok(()));
+        assert_eq!(constraint.check(""), Ok(()));
+        assert_eq!(
+            constraint.check("..#\n#..\n...").unwrap_err().message,
+            "grid 3x3 exceeds U8Vec2 (at most 3x2)"
+        );
+    }
+}
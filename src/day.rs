@@ -0,0 +1,198 @@
+use crate::{
+    constraint::Constraint,
+    part::Part,
+    solution::{parser, Parser, Solution, SolutionPart},
+    AocError, Named, Operation, Params,
+};
+
+/**
+ * Everything needed to run a single day: its embedded inputs and its parts.
+ *
+ * Each day exposes a `day()` function building one of these, which is used
+ * both by its own `main()` and by the `aoc` binary which runs many days.
+ */
+pub struct Day {
+    pub name: &'static str,
+    pub inputs: Vec<(&'static str, &'static str)>,
+    pub entries: Vec<Entry>,
+    pub answers: Option<&'static str>,
+    pub constraints: Vec<Constraint>,
+}
+
+/**
+ * Either a single part which parses the input itself, or a [`Solution`]
+ * whose parts share one parse of the input.
+ *
+ * A part has the defaults of its parameters, and may have variants, which
+ * are other implementations expected to give the same answer. A solution's
+ * parameters are read by its parse, so both parts share them.
+ */
+pub enum Entry {
+    Part {
+        op: Named<Operation>,
+        params: Params,
+        variants: Vec<Named<Operation>>,
+    },
+    Solution {
+        parse: Parser,
+        params: Params,
+    },
+}
+
+pub const DEFAULT_INPUT_LABEL: &str = "input";
+
+impl Day {
+    /**
+     * Creates a day named after the last component of `module_path`, so that
+     * `day01` is named the same whether it's built as its own binary or as a
+     * module of the `aoc` binary.
+     */
+    pub fn create(module_path: &'static str) -> Self {
+        let name = module_path.rsplit("::").next().unwrap_or(module_path);
+        Self {
+            name,
+            inputs: Vec::new(),
+            entries: Vec::new(),
+            answers: None,
+            constraints: Vec::new(),
+        }
+    }
+
+    pub fn input(self, input: &'static str) -> Self {
+        self.labelled_input(DEFAULT_INPUT_LABEL, input)
+    }
+
+    pub fn labelled_input(mut self, label: &'static str, input: &'static str) -> Self {
+        self.inputs.push((label, input));
+        self
+    }
+
+    /**
+     * Sets the contents of the day's `answers.toml`. Its top level keys are
+     * the answers for the first input, and each table holds the answers for
+     * the input with the same label.
+     */
+    pub fn answers(mut self, answers: &'static str) -> Self {
+        self.answers = Some(answers);
+        self
+    }
+
+    /**
+     * Declares a limit of the solution which every input must be within.
+     */
+    pub fn constraint(mut self, constraint: Constraint) -> Self {
+        self.constraints.push(constraint);
+        self
+    }
+
+    /**
+     * Checks `input` against each constraint, returning the first violated.
+     */
+    pub fn validate(&self, input: &str) -> Result<(), AocError> {
+        self.constraints.iter().try_for_each(|c| c.check(input))
+    }
+
+    pub fn part<Args, P: Part<Args>>(self, op: &Named<P>) -> Self {
+        self.part_with_params(op, &[])
+    }
+
+    /**
+     * Adds a part which takes parameters, declaring their default values.
+     */
+    pub fn part_with_params<Args, P: Part<Args>>(
+        mut self,
+        op: &Named<P>,
+        defaults: &[(&str, &str)],
+    ) -> Self {
+        self.entries.push(Entry::Part {
+            op: operation(op),
+            params: Params::create(defaults),
+            variants: Vec::new(),
+        });
+        self
+    }
+
+    /**
+     * Adds another implementation of the last part added, such as a simpler
+     * but slower one. The runner runs every variant with the part's
+     * parameters and flags any answer which differs from the part's.
+     */
+    pub fn variant<Args, P: Part<Args>>(mut self, op: &Named<P>) -> Self {
+        match self.entries.last_mut() {
+            Some(Entry::Part { variants, .. }) => variants.push(operation(op)),
+            _ => panic!("variant {} must follow the part it's a variant of", op.name),
+        }
+        self
+    }
+
+    pub fn solution<S: Solution>(self) -> Self {
+        self.solution_with_params::<S>(&[])
+    }
+
+    /**
+     * Adds a solution with the defaults of the parameters its parse reads.
+     */
+    pub fn solution_with_params<S: Solution>(mut self, defaults: &[(&str, &str)]) -> Self {
+        self.entries.push(Entry::Solution {
+            parse: parser::<S>(),
+            params: Params::create(defaults),
+        });
+        self
+    }
+
+    /**
+     * The names of the parts which can be picked on the command line, in the
+     * order they run, with each part's variants after it.
+     */
+    pub fn part_names(&self) -> Vec<&'static str> {
+        self.entries
+            .iter()
+            .flat_map(|entry| match entry {
+                Entry::Part { op, variants, .. } => std::iter::once(op.name)
+                    .chain(variants.iter().map(|variant| variant.name))
+                    .collect(),
+                Entry::Solution { .. } => SolutionPart::ALL.map(SolutionPart::name).to_vec(),
+            })
+            .collect()
+    }
+
+    /**
+     * The puzzle number, parsed from the name, e.g. `5` for `day05`.
+     */
+    pub fn number(&self) -> Option<u32> {
+        self.name.strip_prefix("day")?.parse().ok()
+    }
+}
+
+fn operation<Args, P: Part<Args>>(op: &Named<P>) -> Named<Operation> {
+    let part = op.wrapped;
+    Named::create(
+        Box::new(move |input, params| part.call(input, params)),
+        op.name,
+    )
+}
+
+#[macro_export]
+macro_rules! create_day {
+    () => {
+        $crate::Day::create(module_path!())
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn name_from_module_path() {
+        assert_eq!(Day::create("day05").name, "day05");
+        assert_eq!(Day::create("aoc::day05").name, "day05");
+    }
+
+    #[test]
+    fn number() {
+        assert_eq!(Day::create("aoc::day05").number(), Some(5));
+        assert_eq!(Day::create("day25").number(), Some(25));
+        assert_eq!(Day::create("template").number(), None);
+    }
+}
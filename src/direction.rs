@@ -0,0 +1,378 @@
+use glam::{I16Vec2, I64Vec2, I8Vec2, IVec2, U16Vec2, U64Vec2, U8Vec2, UVec2};
+use std::{fmt, str::FromStr};
+
+/**
+ * An integer vector which a direction can move, which is any of glam's.
+ * `y` counts down, as rows of the input do, so north is `(0, -1)`.
+ */
+pub trait Step: Copy {
+    /**
+     * The vector from `(dx, dy)`. Negative components of unsigned vectors
+     * wrap around, so add them with `wrapping_add`.
+     */
+    fn offset(dx: i8, dy: i8) -> Self;
+
+    /**
+     * Moves by `(dx, dy)`, or `None` when that leaves the range of the type,
+     * such as going north from row 0 of an unsigned vector.
+     */
+    fn checked_step(self, dx: i8, dy: i8) -> Option<Self>;
+}
+
+macro_rules! step {
+    ( $add:ident, $(($vec:ty, $component:ty)),+ ) => {
+        $(
+            impl Step for $vec {
+                fn offset(dx: i8, dy: i8) -> Self {
+                    Self::new(dx as $component, dy as $component)
+                }
+
+                fn checked_step(self, dx: i8, dy: i8) -> Option<Self> {
+                    Some(Self::new(self.x.$add(dx.into())?, self.y.$add(dy.into())?))
+                }
+            }
+        )+
+    };
+}
+
+step!(
+    checked_add,
+    (I8Vec2, i8),
+    (I16Vec2, i16),
+    (IVec2, i32),
+    (I64Vec2, i64)
+);
+step!(
+    checked_add_signed,
+    (U8Vec2, u8),
+    (U16Vec2, u16),
+    (UVec2, u32),
+    (U64Vec2, u64)
+);
+
+/**
+ * One of the four compass directions on a map, clockwise from north:
+ *
+ * ```
+ * use advent_of_code::direction::Direction4;
+ * use glam::I16Vec2;
+ *
+ * let dir = Direction4::try_from('>').unwrap();
+ * assert_eq!(dir, Direction4::East);
+ * assert_eq!(dir.clockwise(), Direction4::South);
+ * assert_eq!(I16Vec2::new(3, 3) + dir.reverse().offset::<I16Vec2>(), I16Vec2::new(2, 3));
+ * ```
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction4 {
+    North,
+    East,
+    South,
+    West,
+}
+
+/**
+ * One of the eight compass directions on a map, including diagonals,
+ * clockwise from north.
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction8 {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction4 {
+    /**
+     * Every direction in the order of their [`Direction4::index`].
+     */
+    pub const ALL: [Self; 4] = [Self::North, Self::East, Self::South, Self::West];
+
+    /**
+     * A dense index from 0 to 3, for arrays with an entry per direction.
+     */
+    pub const fn index(self) -> usize {
+        self as usize
+    }
+
+    /**
+     * The direction with an index, wrapping around past 3.
+     */
+    pub const fn from_index(index: usize) -> Self {
+        Self::ALL[index % Self::ALL.len()]
+    }
+
+    pub const fn clockwise(self) -> Self {
+        Self::from_index(self.index() + 1)
+    }
+
+    pub const fn counter_clockwise(self) -> Self {
+        Self::from_index(self.index() + 3)
+    }
+
+    pub const fn reverse(self) -> Self {
+        Self::from_index(self.index() + 2)
+    }
+
+    /**
+     * The change in `x` and `y` of a step in this direction.
+     */
+    pub const fn delta(self) -> (i8, i8) {
+        Direction8::from_direction4(self).delta()
+    }
+
+    pub fn offset<V: Step>(self) -> V {
+        let (dx, dy) = self.delta();
+        V::offset(dx, dy)
+    }
+
+    /**
+     * The position a step away from `pos`, or `None` outside the range of
+     * its type.
+     */
+    pub fn step<V: Step>(self, pos: V) -> Option<V> {
+        let (dx, dy) = self.delta();
+        pos.checked_step(dx, dy)
+    }
+
+    /**
+     * Parses one of `^>v<`.
+     */
+    pub const fn from_arrow(c: char) -> Option<Self> {
+        match c {
+            '^' => Some(Self::North),
+            '>' => Some(Self::East),
+            'v' => Some(Self::South),
+            '<' => Some(Self::West),
+            _ => None,
+        }
+    }
+
+    /**
+     * Parses a compass letter `NESW`, or a letter of `URDL` for up, right,
+     * down and left.
+     */
+    pub const fn from_letter(c: char) -> Option<Self> {
+        match c {
+            'N' | 'U' => Some(Self::North),
+            'E' | 'R' => Some(Self::East),
+            'S' | 'D' => Some(Self::South),
+            'W' | 'L' => Some(Self::West),
+            _ => None,
+        }
+    }
+
+    pub const fn arrow(self) -> char {
+        ['^', '>', 'v', '<'][self.index()]
+    }
+}
+
+impl Direction8 {
+    /**
+     * Every direction in the order of their [`Direction8::index`].
+     */
+    pub const ALL: [Self; 8] = [
+        Self::North,
+        Self::NorthEast,
+        Self::East,
+        Self::SouthEast,
+        Self::South,
+        Self::SouthWest,
+        Self::West,
+        Self::NorthWest,
+    ];
+
+    /**
+     * A dense index from 0 to 7, for arrays with an entry per direction.
+     */
+    pub const fn index(self) -> usize {
+        self as usize
+    }
+
+    /**
+     * The direction with an index, wrapping around past 7.
+     */
+    pub const fn from_index(index: usize) -> Self {
+        Self::ALL[index % Self::ALL.len()]
+    }
+
+    const fn from_direction4(dir: Direction4) -> Self {
+        Self::from_index(dir.index() * 2)
+    }
+
+    /**
+     * Turns an eighth of the way clockwise.
+     */
+    pub const fn clockwise(self) -> Self {
+        Self::from_index(self.index() + 1)
+    }
+
+    /**
+     * Turns an eighth of the way counter clockwise.
+     */
+    pub const fn counter_clockwise(self) -> Self {
+        Self::from_index(self.index() + 7)
+    }
+
+    pub const fn reverse(self) -> Self {
+        Self::from_index(self.index() + 4)
+    }
+
+    /**
+     * The change in `x` and `y` of a step in this direction.
+     */
+    pub const fn delta(self) -> (i8, i8) {
+        [
+            (0, -1),
+            (1, -1),
+            (1, 0),
+            (1, 1),
+            (0, 1),
+            (-1, 1),
+            (-1, 0),
+            (-1, -1),
+        ][self.index()]
+    }
+
+    pub fn offset<V: Step>(self) -> V {
+        let (dx, dy) = self.delta();
+        V::offset(dx, dy)
+    }
+
+    /**
+     * The position a step away from `pos`, or `None` outside the range of
+     * its type.
+     */
+    pub fn step<V: Step>(self, pos: V) -> Option<V> {
+        let (dx, dy) = self.delta();
+        pos.checked_step(dx, dy)
+    }
+
+    pub const fn name(self) -> &'static str {
+        ["N", "NE", "E", "SE", "S", "SW", "W", "NW"][self.index()]
+    }
+}
+
+impl From<Direction4> for Direction8 {
+    fn from(dir: Direction4) -> Self {
+        Self::from_direction4(dir)
+    }
+}
+
+/**
+ * Parses an arrow like [`Direction4::from_arrow`] or a letter like
+ * [`Direction4::from_letter`].
+ */
+impl TryFrom<char> for Direction4 {
+    type Error = String;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        Self::from_arrow(c)
+            .or(Self::from_letter(c))
+            .ok_or_else(|| format!("expected a direction but found {c}"))
+    }
+}
+
+impl FromStr for Direction4 {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => c.try_into(),
+            _ => Err(format!("expected a direction but found {s}")),
+        }
+    }
+}
+
+/**
+ * Parses a compass name such as `N` or `SW`.
+ */
+impl FromStr for Direction8 {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|dir| dir.name() == s)
+            .ok_or_else(|| format!("expected a compass direction but found {s}"))
+    }
+}
+
+impl fmt::Display for Direction4 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.arrow())
+    }
+}
+
+impl fmt::Display for Direction8 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn turns() {
+        for dir in Direction4::ALL {
+            assert_eq!(dir.clockwise().counter_clockwise(), dir);
+            assert_eq!(dir.clockwise().clockwise(), dir.reverse());
+            assert_eq!(Direction4::from_index(dir.index()), dir);
+        }
+        assert_eq!(Direction4::West.clockwise(), Direction4::North);
+        assert_eq!(Direction8::North.counter_clockwise(), Direction8::NorthWest);
+        assert_eq!(Direction8::SouthWest.reverse(), Direction8::NorthEast);
+        assert_eq!(Direction8::from(Direction4::South), Direction8::South);
+    }
+
+    #[test]
+    fn offsets() {
+        assert_eq!(Direction4::North.offset::<IVec2>(), IVec2::new(0, -1));
+        assert_eq!(Direction8::SouthWest.offset::<I8Vec2>(), I8Vec2::new(-1, 1));
+        let pos = U8Vec2::new(3, 0);
+        assert_eq!(
+            pos.wrapping_add(Direction4::West.offset()),
+            U8Vec2::new(2, 0)
+        );
+        assert_eq!(Direction4::North.step(pos), None);
+        assert_eq!(Direction8::SouthEast.step(pos), Some(U8Vec2::new(4, 1)));
+        assert_eq!(Direction4::East.step(I8Vec2::new(i8::MAX, 0)), None);
+        for dir in Direction8::ALL {
+            let (dx, dy) = dir.delta();
+            assert_eq!(dir.reverse().delta(), (-dx, -dy));
+        }
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!(
+            "^>v<"
+                .chars()
+                .map(Direction4::try_from)
+                .collect::<Result<Vec<_>, _>>(),
+            Ok(Direction4::ALL.to_vec())
+        );
+        assert_eq!(
+            "NESW"
+                .chars()
+                .map(Direction4::try_from)
+                .collect::<Result<Vec<_>, _>>(),
+            Ok(Direction4::ALL.to_vec())
+        );
+        assert_eq!("U".parse(), Ok(Direction4::North));
+        assert_eq!("L".parse(), Ok(Direction4::West));
+        assert!("x".parse::<Direction4>().is_err());
+        assert!("NE".parse::<Direction4>().is_err());
+        assert_eq!("NW".parse(), Ok(Direction8::NorthWest));
+        assert_eq!(Direction4::East.to_string(), ">");
+        assert_eq!(Direction8::SouthEast.to_string(), "SE");
+    }
+}
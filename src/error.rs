@@ -0,0 +1,149 @@
+use crate::Answer;
+use std::{any::Any, error::Error, fmt};
+
+/**
+ * An error found while solving a part, usually because the input isn't in the
+ * expected format. Parts report where in the input the problem is, and the
+ * runner fills in which day and part it came from.
+ *
+ * `line` and `column` are 1-based, like an editor shows them.
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct AocError {
+    pub day: Option<&'static str>,
+    pub part: Option<&'static str>,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    pub message: String,
+}
+
+impl AocError {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            day: None,
+            part: None,
+            line: None,
+            column: None,
+            message: message.into(),
+        }
+    }
+
+    pub fn at_line(self, line: usize) -> Self {
+        Self {
+            line: Some(line),
+            ..self
+        }
+    }
+
+    pub fn at(self, line: usize, column: usize) -> Self {
+        Self {
+            line: Some(line),
+            column: Some(column),
+            ..self
+        }
+    }
+
+    /**
+     * Sets the day and part, unless the error already has them.
+     */
+    pub fn in_part(self, day: &'static str, part: &'static str) -> Self {
+        Self {
+            day: self.day.or(Some(day)),
+            part: self.part.or(Some(part)),
+            ..self
+        }
+    }
+
+    /**
+     * Converts the payload of a caught panic, for parts which still panic on
+     * bad input.
+     */
+    pub fn from_panic(payload: Box<dyn Any + Send>) -> Self {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".to_owned());
+        Self::new(format!("panicked: {message}"))
+    }
+}
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let source = [self.day, self.part]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
+        if !source.is_empty() {
+            write!(f, "{}: ", source.join(" "))?;
+        }
+        let location = [
+            self.line.map(|line| format!("line {line}")),
+            self.column.map(|column| format!("column {column}")),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+        if !location.is_empty() {
+            write!(f, "{}: ", location.join(", "))?;
+        }
+        f.write_str(&self.message)
+    }
+}
+
+impl Error for AocError {}
+
+/**
+ * What a part may return: either anything which converts to an [`Answer`],
+ * or a result which is that unless the part failed.
+ */
+pub trait Outcome {
+    fn into_result(self) -> Result<Answer, AocError>;
+}
+
+impl<T: Into<Answer>> Outcome for T {
+    fn into_result(self) -> Result<Answer, AocError> {
+        Ok(self.into())
+    }
+}
+
+impl<T: Into<Answer>> Outcome for Result<T, AocError> {
+    fn into_result(self) -> Result<Answer, AocError> {
+        self.map(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display() {
+        assert_eq!(AocError::new("bad").to_string(), "bad");
+        assert_eq!(
+            AocError::new("unexpected char x")
+                .at(3, 5)
+                .in_part("day06", "part1")
+                .to_string(),
+            "day06 part1: line 3, column 5: unexpected char x"
+        );
+        assert_eq!(
+            AocError::new("missing size").at_line(1).to_string(),
+            "line 1: missing size"
+        );
+    }
+
+    #[test]
+    fn in_part_keeps_existing() {
+        let error = AocError::new("bad")
+            .in_part("day01", "part1")
+            .in_part("day02", "part2");
+        assert_eq!((error.day, error.part), (Some("day01"), Some("part1")));
+    }
+
+    #[test]
+    fn from_panic() {
+        let payload = std::panic::catch_unwind(|| panic!("oops {}", 1)).unwrap_err();
+        assert_eq!(AocError::from_panic(payload).message, "panicked: oops 1");
+    }
+}
@@ -0,0 +1,235 @@
+use crate::{
+    part::{self, Part},
+    solution::{self, Solution, SolutionPart},
+    Answer, AocError, Params,
+};
+
+/**
+ * Declares the examples of a day as a table, generating a test for each part
+ * of each example, named after both, such as `tests::example2::part1`:
+ *
+ * ```ignore
+ * aoc_examples! {
+ *     example1: "example.txt" => { part1: "4", part2: 9 },
+ *     small: text "1\n2\n3\n2024" => { part2: "23" },
+ *     sized: "example.txt" with Params::create(PARAMS).with("width", 11) => { part1: "12" },
+ *     parsed: "example.txt" => MySolution { part1: 4, part2: 9 },
+ * }
+ * ```
+ *
+ * Each example reads a file next to the day's `main.rs`, or takes its input
+ * inline with `text`, and can pass [`Params`] to its parts with `with`.
+ * Naming a [`Solution`] before the parts checks its parts instead of the
+ * day's part functions.
+ * Expected answers are anything which converts into an [`Answer`], and a
+ * wrong answer fails with a diff of the expected and actual answers.
+ */
+#[macro_export]
+macro_rules! aoc_examples {
+    () => {};
+    (@params) => {
+        $crate::Params::default()
+    };
+    (@params $params:expr) => {
+        $params
+    };
+    (@example $name:ident, $input:expr, $params:expr, [$solution:ident], $($part:ident : $expected:expr),+) => {
+        mod $name {
+            #[allow(unused_imports)]
+            use super::*;
+            $(
+                #[test]
+                fn $part() {
+                    $crate::examples::check_solution::<super::$solution>(
+                        std::concat!(std::module_path!(), "::", std::stringify!($part)),
+                        std::stringify!($part),
+                        $input,
+                        &$params,
+                        $crate::Answer::from($expected),
+                    );
+                }
+            )+
+        }
+    };
+    (@example $name:ident, $input:expr, $params:expr, [], $($part:ident : $expected:expr),+) => {
+        mod $name {
+            #[allow(unused_imports)]
+            use super::*;
+            $(
+                #[test]
+                fn $part() {
+                    $crate::examples::check(
+                        std::concat!(std::module_path!(), "::", std::stringify!($part)),
+                        &super::$part,
+                        $input,
+                        &$params,
+                        $crate::Answer::from($expected),
+                    );
+                }
+            )+
+        }
+    };
+    (
+        $name:ident : text $input:literal $(with $params:expr)?
+            => $($solution:ident)? { $($part:ident : $expected:expr),+ $(,)? }
+        $(, $($rest:tt)*)?
+    ) => {
+        $crate::aoc_examples!(
+            @example $name, $input, $crate::aoc_examples!(@params $($params)?), [$($solution)?],
+            $($part: $expected),+
+        );
+        $( $crate::aoc_examples!($($rest)*); )?
+    };
+    (
+        $name:ident : $file:literal $(with $params:expr)?
+            => $($solution:ident)? { $($part:ident : $expected:expr),+ $(,)? }
+        $(, $($rest:tt)*)?
+    ) => {
+        $crate::aoc_examples!(
+            @example $name, include_str!($file), $crate::aoc_examples!(@params $($params)?),
+            [$($solution)?], $($part: $expected),+
+        );
+        $( $crate::aoc_examples!($($rest)*); )?
+    };
+}
+
+/**
+ * Runs a part on an example for [`crate::aoc_examples!`], panicking with a
+ * diff when it doesn't give the expected answer.
+ */
+pub fn check<Args, P: Part<Args>>(
+    test: &str,
+    part: &P,
+    input: &str,
+    params: &Params,
+    expected: Answer,
+) {
+    compare(test, part::call(part, input, params), expected);
+}
+
+/**
+ * Runs the part of a [`Solution`] named `part` on an example, like
+ * [`check`], parsing the example with `params`.
+ */
+pub fn check_solution<S: Solution>(
+    test: &str,
+    part: &str,
+    input: &str,
+    params: &Params,
+    expected: Answer,
+) {
+    let part = SolutionPart::ALL
+        .into_iter()
+        .find(|p| p.name() == part)
+        .unwrap_or_else(|| panic!("{test}: solutions have no part {part}"));
+    let result = solution::parser::<S>()(input, params).map(|solver| solver(part));
+    compare(test, result, expected);
+}
+
+fn compare(test: &str, result: Result<Answer, AocError>, expected: Answer) {
+    match result {
+        Ok(answer) if answer == expected => {}
+        Ok(answer) => panic!("{test} gave the wrong answer\n{}", diff(&expected, &answer)),
+        Err(e) => panic!("{test} failed: {e}"),
+    }
+}
+
+/**
+ * Shows how an answer differs from the expected one. Answers on several
+ * lines, such as pictures, are compared line by line.
+ */
+pub fn diff(expected: &Answer, actual: &Answer) -> String {
+    let (expected_text, actual_text) = match (expected.to_string(), actual.to_string()) {
+        // Only the kinds of answer differ, such as text which looks like a number.
+        (e, a) if e == a => (format!("{expected:?}"), format!("{actual:?}")),
+        texts => texts,
+    };
+    if !expected_text.contains('\n') && !actual_text.contains('\n') {
+        return format!("expected: {expected_text}\n  actual: {actual_text}");
+    }
+    let expected_lines = expected_text.lines().collect::<Vec<_>>();
+    let actual_lines = actual_text.lines().collect::<Vec<_>>();
+    let mut diff = vec!["- expected".to_owned(), "+ actual".to_owned()];
+    for index in 0..expected_lines.len().max(actual_lines.len()) {
+        match (expected_lines.get(index), actual_lines.get(index)) {
+            (Some(e), Some(a)) if e == a => diff.push(format!("  {e}")),
+            (e, a) => {
+                diff.extend(e.map(|e| format!("- {e}")));
+                diff.extend(a.map(|a| format!("+ {a}")));
+            }
+        }
+    }
+    diff.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::Lines;
+
+    const SCALE: &[(&str, &str)] = &[("scale", "2")];
+
+    fn count(input: Lines) -> usize {
+        input.count()
+    }
+
+    fn scaled(input: Lines, params: &Params) -> Result<usize, AocError> {
+        Ok(input.count() * params.get::<usize>("scale")?)
+    }
+
+    struct Lengths;
+
+    impl Solution for Lengths {
+        type Parsed = Vec<usize>;
+
+        fn parse(input: Lines, params: &Params) -> Result<Self::Parsed, AocError> {
+            let scale = params.get::<usize>("scale")?;
+            Ok(input.map(|line| line.len() * scale).collect())
+        }
+
+        fn part1(parsed: &Self::Parsed) -> impl Into<Answer> {
+            parsed.len()
+        }
+
+        fn part2(parsed: &Self::Parsed) -> impl Into<Answer> {
+            parsed.iter().sum::<usize>()
+        }
+    }
+
+    crate::aoc_examples! {
+        solution: text "ab\nc" with Params::create(SCALE) => Lengths { part1: 2, part2: 6 },
+        default_params: text "a\nb\nc" with Params::create(SCALE) => { count: 3, scaled: 6 },
+        with_params: text "a" with Params::create(SCALE).with("scale", 5) => { scaled: 5 },
+    }
+
+    #[test]
+    fn diff_answers() {
+        assert_eq!(
+            diff(&Answer::Integer(4), &Answer::Integer(5)),
+            "expected: 4\n  actual: 5"
+        );
+        assert_eq!(
+            diff(&Answer::Integer(4), &Answer::Text("4".to_owned())),
+            "expected: Integer(4)\n  actual: Text(\"4\")"
+        );
+        assert_eq!(
+            diff(
+                &Answer::picture(["#.", ".#", ".."]),
+                &Answer::picture(["#.", "##"])
+            ),
+            "- expected\n+ actual\n  #.\n- .#\n+ ##\n- .."
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "count gave the wrong answer\nexpected: 2\n  actual: 3")]
+    fn wrong_answer() {
+        check(
+            "count",
+            &count,
+            "a\nb\nc",
+            &Params::default(),
+            Answer::from(2),
+        );
+    }
+}
@@ -0,0 +1,199 @@
+use crate::{runner::Record, Answer, AocError};
+use std::{str::FromStr, time::Duration};
+
+/**
+ * How the runner prints records. `Text` is meant for people, while `Json` and
+ * `Tsv` print one line per record for other tools to ingest.
+ */
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+    Tsv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            "tsv" => Ok(Self::Tsv),
+            _ => Err(format!("unknown format {s}, expected text, json or tsv")),
+        }
+    }
+}
+
+/**
+ * The columns of [`tsv_record`], in order. Times are in nanoseconds.
+ */
+pub const TSV_HEADER: &str = "module\tpart\tinput\tanswer\tparse_ns\ttime_ns\tstatus\terror\tcheck";
+
+fn nanos(duration: Option<Duration>) -> Option<u128> {
+    duration.map(|d| d.as_nanos())
+}
+
+/**
+ * The error without its day and part, which are already fields of the record.
+ */
+fn error_message(record: &Record) -> Option<String> {
+    record.error.as_ref().map(|e| {
+        AocError {
+            day: None,
+            part: None,
+            ..e.clone()
+        }
+        .to_string()
+    })
+}
+
+/**
+ * Formats a record as a single line JSON object. Times are in nanoseconds,
+ * and fields which don't apply are `null`.
+ */
+pub fn json_record(record: &Record) -> String {
+    let string = |s: Option<&str>| s.map_or("null".to_owned(), json_string);
+    let number = |n: Option<u128>| n.map_or("null".to_owned(), |n| n.to_string());
+    let fields = [
+        ("module", json_string(record.day)),
+        ("part", json_string(record.part)),
+        ("input", json_string(&record.input)),
+        (
+            "answer",
+            string(record.answer.as_ref().map(Answer::to_string).as_deref()),
+        ),
+        ("parse_ns", number(nanos(record.parse))),
+        ("time_ns", number(nanos(record.elapsed))),
+        ("status", json_string(record.status())),
+        ("error", string(error_message(record).as_deref())),
+        (
+            "check",
+            string(record.check.as_ref().map(|c| c.to_string()).as_deref()),
+        ),
+    ];
+    let fields = fields
+        .iter()
+        .map(|(name, value)| format!("\"{name}\":{value}"))
+        .collect::<Vec<_>>();
+    format!("{{{}}}", fields.join(","))
+}
+
+fn json_string(s: &str) -> String {
+    let mut result = String::with_capacity(s.len() + 2);
+    result.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c if c.is_control() => result.push_str(&format!("\\u{:04x}", c as u32)),
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
+
+/**
+ * Formats a record as a line of tab separated values, matching [`TSV_HEADER`].
+ * Fields which don't apply are empty.
+ */
+pub fn tsv_record(record: &Record) -> String {
+    let number = |n: Option<u128>| n.map(|n| n.to_string()).unwrap_or_default();
+    [
+        tsv_field(record.day),
+        tsv_field(record.part),
+        tsv_field(&record.input),
+        tsv_field(
+            &record
+                .answer
+                .as_ref()
+                .map(Answer::to_string)
+                .unwrap_or_default(),
+        ),
+        number(nanos(record.parse)),
+        number(nanos(record.elapsed)),
+        record.status().to_owned(),
+        tsv_field(&error_message(record).unwrap_or_default()),
+        tsv_field(
+            &record
+                .check
+                .as_ref()
+                .map(|c| c.to_string())
+                .unwrap_or_default(),
+        ),
+    ]
+    .join("\t")
+}
+
+/**
+ * Escapes the characters which would break up a tsv line.
+ */
+fn tsv_field(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::answers::Check;
+
+    fn record() -> Record {
+        Record {
+            day: "day01",
+            part: "part1",
+            input: "input".to_owned(),
+            answer: Some(Answer::Text("a\tb\n\"c\"".to_owned())),
+            parse: None,
+            elapsed: Some(Duration::from_micros(12)),
+            bench: None,
+            check: Some(Check::Pass),
+            error: None,
+            variant_of: None,
+        }
+    }
+
+    #[test]
+    fn parse_format() {
+        assert_eq!("json".parse(), Ok(Format::Json));
+        assert_eq!("tsv".parse(), Ok(Format::Tsv));
+        assert!("xml".parse::<Format>().is_err());
+    }
+
+    #[test]
+    fn json() {
+        assert_eq!(
+            json_record(&record()),
+            r#"{"module":"day01","part":"part1","input":"input","answer":"a\tb\n\"c\"","parse_ns":null,"time_ns":12000,"status":"ok","error":null,"check":"PASS"}"#
+        );
+    }
+
+    #[test]
+    fn tsv() {
+        assert_eq!(
+            tsv_record(&record()),
+            "day01\tpart1\tinput\ta\\tb\\n\"c\"\t\t12000\tok\t\tPASS"
+        );
+        let error = Record {
+            answer: None,
+            check: None,
+            error: Some(AocError::new("bad").at_line(2).in_part("day01", "part1")),
+            ..record()
+        };
+        assert_eq!(
+            tsv_record(&error),
+            "day01\tpart1\tinput\t\t\t12000\terror\tline 2: bad\t"
+        );
+        assert_eq!(
+            tsv_record(&error).split('\t').count(),
+            TSV_HEADER.split('\t').count()
+        );
+    }
+}
@@ -0,0 +1,392 @@
+use crate::pathfinding::bfs;
+use std::{collections::HashMap, hash::Hash};
+
+/**
+ * The id of a node in a [`Graph`], numbered from 0 in the order their names
+ * were first added.
+ */
+pub type NodeId = u32;
+
+/**
+ * A set of nodes as a bitset, with a bit per [`NodeId`].
+ */
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct NodeSet(Vec<u64>);
+
+impl NodeSet {
+    fn insert(&mut self, id: NodeId) {
+        let (word, bit) = (id as usize / 64, id % 64);
+        if word >= self.0.len() {
+            self.0.resize(word + 1, 0);
+        }
+        self.0[word] |= 1 << bit;
+    }
+
+    fn remove(&mut self, id: NodeId) {
+        if let Some(word) = self.0.get_mut(id as usize / 64) {
+            *word &= !(1 << (id % 64));
+        }
+    }
+
+    fn contains(&self, id: NodeId) -> bool {
+        self.0
+            .get(id as usize / 64)
+            .is_some_and(|word| word & (1 << (id % 64)) != 0)
+    }
+
+    fn len(&self) -> usize {
+        self.0.iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    fn intersection(&self, other: &Self) -> Self {
+        Self(self.0.iter().zip(&other.0).map(|(a, b)| a & b).collect())
+    }
+
+    fn difference(&self, other: &Self) -> Self {
+        let other = other.0.iter().chain(std::iter::repeat(&0));
+        Self(self.0.iter().zip(other).map(|(a, b)| a & !b).collect())
+    }
+
+    fn iter(&self) -> impl Iterator<Item = NodeId> + '_ {
+        self.0.iter().enumerate().flat_map(|(index, &word)| {
+            (0..64)
+                .filter(move |bit| word & (1 << bit) != 0)
+                .map(move |bit| (index * 64 + bit) as NodeId)
+        })
+    }
+}
+
+impl FromIterator<NodeId> for NodeSet {
+    fn from_iter<I: IntoIterator<Item = NodeId>>(iter: I) -> Self {
+        let mut set = Self::default();
+        for id in iter {
+            set.insert(id);
+        }
+        set
+    }
+}
+
+/**
+ * An undirected graph whose nodes are named by anything hashable, such as
+ * the names of computers in the input. Names are interned as compact
+ * [`NodeId`]s, and each node's neighbours are a bitset, which keeps finding
+ * cliques quick:
+ *
+ * ```
+ * use advent_of_code::graph::Graph;
+ *
+ * let graph: Graph<&str> = [("a", "b"), ("b", "c"), ("c", "a"), ("c", "d")].into_iter().collect();
+ * let names = |ids: Vec<u32>| ids.into_iter().map(|id| *graph.name(id)).collect::<Vec<_>>();
+ * assert_eq!(graph.triangles().len(), 1);
+ * assert_eq!(names(graph.maximum_clique()), ["a", "b", "c"]);
+ * ```
+ */
+#[derive(Clone, Debug)]
+pub struct Graph<N> {
+    names: Vec<N>,
+    ids: HashMap<N, NodeId>,
+    adjacent: Vec<NodeSet>,
+}
+
+impl<N: Clone + Eq + Hash> Default for Graph<N> {
+    fn default() -> Self {
+        Self {
+            names: Vec::new(),
+            ids: HashMap::new(),
+            adjacent: Vec::new(),
+        }
+    }
+}
+
+impl<N: Clone + Eq + Hash> Graph<N> {
+    /**
+     * The id of the node called `name`, adding it if it's new.
+     */
+    pub fn add_node(&mut self, name: N) -> NodeId {
+        if let Some(&id) = self.ids.get(&name) {
+            return id;
+        }
+        let id = self.names.len() as NodeId;
+        self.names.push(name.clone());
+        self.ids.insert(name, id);
+        self.adjacent.push(NodeSet::default());
+        id
+    }
+
+    /**
+     * Connects the nodes called `a` and `b`, adding them if they're new, and
+     * returns their ids.
+     */
+    pub fn add_edge(&mut self, a: N, b: N) -> (NodeId, NodeId) {
+        let (a, b) = (self.add_node(a), self.add_node(b));
+        self.adjacent[a as usize].insert(b);
+        self.adjacent[b as usize].insert(a);
+        (a, b)
+    }
+
+    pub fn id(&self, name: &N) -> Option<NodeId> {
+        self.ids.get(name).copied()
+    }
+}
+
+impl<N> Graph<N> {
+    /**
+     * Panics when `id` isn't a node of this graph.
+     */
+    pub fn name(&self, id: NodeId) -> &N {
+        &self.names[id as usize]
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = NodeId> {
+        0..self.len() as NodeId
+    }
+
+    pub fn are_adjacent(&self, a: NodeId, b: NodeId) -> bool {
+        self.adjacent[a as usize].contains(b)
+    }
+
+    pub fn neighbors(&self, id: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        self.adjacent[id as usize].iter()
+    }
+
+    pub fn degree(&self, id: NodeId) -> usize {
+        self.adjacent[id as usize].len()
+    }
+
+    /**
+     * Every set of three nodes which are all connected to each other, with
+     * the ids of each in increasing order.
+     */
+    pub fn triangles(&self) -> Vec<[NodeId; 3]> {
+        self.cliques(3)
+            .into_iter()
+            .map(|clique| [clique[0], clique[1], clique[2]])
+            .collect()
+    }
+
+    /**
+     * Every set of `k` nodes which are all connected to each other, with the
+     * ids of each in increasing order.
+     */
+    pub fn cliques(&self, k: usize) -> Vec<Vec<NodeId>> {
+        let mut cliques = Vec::new();
+        self.extend_cliques(&mut Vec::new(), self.nodes().collect(), k, &mut cliques);
+        cliques
+    }
+
+    /**
+     * Adds each node of `candidates` to `clique`, continuing only with the
+     * later candidates which are connected to it, so each clique is found
+     * once.
+     */
+    fn extend_cliques(
+        &self,
+        clique: &mut Vec<NodeId>,
+        mut candidates: NodeSet,
+        k: usize,
+        cliques: &mut Vec<Vec<NodeId>>,
+    ) {
+        if clique.len() == k {
+            cliques.push(clique.clone());
+            return;
+        }
+        for id in candidates.iter().collect::<Vec<_>>() {
+            candidates.remove(id);
+            clique.push(id);
+            let next = candidates.intersection(&self.adjacent[id as usize]);
+            self.extend_cliques(clique, next, k, cliques);
+            clique.pop();
+        }
+    }
+
+    /**
+     * The largest set of nodes which are all connected to each other, with
+     * their ids in increasing order. When several are the largest, this is
+     * one of them.
+     */
+    pub fn maximum_clique(&self) -> Vec<NodeId> {
+        let mut largest = Vec::new();
+        self.bron_kerbosch(
+            &mut Vec::new(),
+            self.nodes().collect(),
+            NodeSet::default(),
+            &mut largest,
+        );
+        largest.sort();
+        largest
+    }
+
+    /**
+     * The Bron–Kerbosch algorithm, which grows `clique` with nodes of
+     * `candidates` until it's maximal, skipping nodes of `excluded` whose
+     * cliques were already found. Only nodes which aren't neighbours of a
+     * pivot need trying, since any clique with a neighbour could have
+     * included the pivot instead.
+     */
+    fn bron_kerbosch(
+        &self,
+        clique: &mut Vec<NodeId>,
+        mut candidates: NodeSet,
+        mut excluded: NodeSet,
+        largest: &mut Vec<NodeId>,
+    ) {
+        if clique.len() + candidates.len() <= largest.len() {
+            return;
+        }
+        let Some(pivot) = candidates
+            .iter()
+            .chain(excluded.iter())
+            .max_by_key(|&id| candidates.intersection(&self.adjacent[id as usize]).len())
+        else {
+            // nothing else connects to all of the clique, so it's maximal
+            *largest = clique.clone();
+            return;
+        };
+        for id in candidates
+            .difference(&self.adjacent[pivot as usize])
+            .iter()
+            .collect::<Vec<_>>()
+        {
+            let adjacent = &self.adjacent[id as usize];
+            clique.push(id);
+            self.bron_kerbosch(
+                clique,
+                candidates.intersection(adjacent),
+                excluded.intersection(adjacent),
+                largest,
+            );
+            clique.pop();
+            candidates.remove(id);
+            excluded.insert(id);
+        }
+    }
+
+    /**
+     * The sets of nodes connected to each other by some path, each with ids
+     * in increasing order, in the order of their smallest id.
+     */
+    pub fn connected_components(&self) -> Vec<Vec<NodeId>> {
+        let mut found = NodeSet::default();
+        let mut components = Vec::new();
+        for id in self.nodes() {
+            if !found.contains(id) {
+                let paths = bfs(id, |&id| self.neighbors(id));
+                let mut component = paths.costs().map(|(&id, _)| id).collect::<Vec<_>>();
+                component.sort();
+                for &id in &component {
+                    found.insert(id);
+                }
+                components.push(component);
+            }
+        }
+        components
+    }
+}
+
+impl<N: Clone + Eq + Hash> FromIterator<(N, N)> for Graph<N> {
+    /**
+     * Builds a graph from its edges.
+     */
+    fn from_iter<I: IntoIterator<Item = (N, N)>>(edges: I) -> Self {
+        let mut graph = Self::default();
+        for (a, b) in edges {
+            graph.add_edge(a, b);
+        }
+        graph
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /**
+     * Two squares with both diagonals, `a` to `d` and `e` to `h`, joined by
+     * `d-e`, which also has a triangle `e-f-i`, and a separate edge `x-y`.
+     */
+    fn example() -> Graph<char> {
+        let mut graph = Graph::default();
+        for square in ["abcd", "efgh"] {
+            for (a, b) in square.chars().zip(square.chars().skip(1)) {
+                graph.add_edge(a, b);
+            }
+            let corners = square.chars().collect::<Vec<_>>();
+            graph.add_edge(corners[3], corners[0]);
+            graph.add_edge(corners[0], corners[2]);
+            graph.add_edge(corners[1], corners[3]);
+        }
+        for (a, b) in [('d', 'e'), ('e', 'i'), ('f', 'i'), ('x', 'y')] {
+            graph.add_edge(a, b);
+        }
+        graph
+    }
+
+    fn names(graph: &Graph<char>, ids: &[NodeId]) -> String {
+        ids.iter().map(|&id| graph.name(id)).collect()
+    }
+
+    #[test]
+    fn interned() {
+        let mut graph = example();
+        assert_eq!(graph.len(), 11);
+        assert_eq!(graph.id(&'a'), Some(0));
+        assert_eq!(graph.id(&'z'), None);
+        assert_eq!(graph.add_node('c'), 2);
+        assert!(graph.are_adjacent(0, 2));
+        assert!(!graph.are_adjacent(0, 4));
+        assert_eq!(
+            names(&graph, &graph.neighbors(4).collect::<Vec<_>>()),
+            "dfghi"
+        );
+        assert_eq!(graph.degree(4), 5);
+    }
+
+    #[test]
+    fn cliques() {
+        let graph = example();
+        // 4 in each square and e-f-i
+        assert_eq!(graph.triangles().len(), 9);
+        let fours = graph.cliques(4);
+        assert_eq!(
+            fours
+                .iter()
+                .map(|ids| names(&graph, ids))
+                .collect::<Vec<_>>(),
+            ["abcd", "efgh"]
+        );
+        assert!(graph.cliques(5).is_empty());
+        let largest = names(&graph, &graph.maximum_clique());
+        assert!(["abcd", "efgh"].contains(&largest.as_str()), "{largest}");
+    }
+
+    #[test]
+    fn components() {
+        let graph = example();
+        let components = graph.connected_components();
+        assert_eq!(
+            components
+                .iter()
+                .map(|ids| names(&graph, ids))
+                .collect::<Vec<_>>(),
+            ["abcdefghi", "xy"]
+        );
+    }
+
+    #[test]
+    fn node_set() {
+        let set = [3, 70, 130].into_iter().collect::<NodeSet>();
+        let other = [3, 64].into_iter().collect::<NodeSet>();
+        assert_eq!(set.len(), 3);
+        assert!(set.contains(70) && !set.contains(4) && !set.contains(1000));
+        assert_eq!(set.intersection(&other).iter().collect::<Vec<_>>(), [3]);
+        assert_eq!(set.difference(&other).iter().collect::<Vec<_>>(), [70, 130]);
+    }
+}
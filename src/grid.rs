@@ -0,0 +1,414 @@
+use crate::{
+    direction::{Direction4, Direction8},
+    AocError,
+};
+use glam::{I16Vec2, I64Vec2, I8Vec2, IVec2, U16Vec2, U64Vec2, U8Vec2, UVec2};
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+};
+
+/**
+ * A position in a [`Grid`], which is any of glam's integer vectors so that
+ * each day can use whichever fits its input best. `x` is the column and `y`
+ * is the row, counting down from the first line of the input.
+ */
+pub trait Position: Copy {
+    /**
+     * The column and row, or `None` when either is negative.
+     */
+    fn column_row(self) -> Option<(usize, usize)>;
+
+    /**
+     * Panics when the column or row don't fit, which [`Grid::parse`] avoids
+     * by checking the size of the grid first.
+     */
+    fn from_column_row(column: usize, row: usize) -> Self;
+
+    /**
+     * Whether every position of a grid of `width` by `height` fits in this
+     * type. Its size, one past the last position, may not.
+     */
+    fn fits(width: usize, height: usize) -> bool;
+}
+
+macro_rules! position {
+    ( $(($vec:ty, $component:ty)),+ ) => {
+        $(
+            impl Position for $vec {
+                fn column_row(self) -> Option<(usize, usize)> {
+                    Some((self.x.try_into().ok()?, self.y.try_into().ok()?))
+                }
+
+                fn from_column_row(column: usize, row: usize) -> Self {
+                    Self::new(
+                        column.try_into().expect("column fits"),
+                        row.try_into().expect("row fits"),
+                    )
+                }
+
+                fn fits(width: usize, height: usize) -> bool {
+                    let fits = |n: usize| <$component>::try_from(n.saturating_sub(1)).is_ok();
+                    fits(width) && fits(height)
+                }
+            }
+        )+
+    };
+}
+
+position!(
+    (I8Vec2, i8),
+    (I16Vec2, i16),
+    (IVec2, i32),
+    (I64Vec2, i64),
+    (U8Vec2, u8),
+    (U16Vec2, u16),
+    (UVec2, u32),
+    (U64Vec2, u64)
+);
+
+/**
+ * A rectangle of cells, stored row by row, such as a map parsed from the
+ * puzzle input:
+ *
+ * ```
+ * use advent_of_code::grid::Grid;
+ * use glam::IVec2;
+ *
+ * let mut start = None;
+ * let grid = Grid::parse_with_position("#S.\n..#".lines(), |pos: IVec2, c| match c {
+ *     'S' => {
+ *         start = Some(pos);
+ *         Some(false)
+ *     }
+ *     c => Some(c == '#'),
+ * })
+ * .unwrap();
+ * assert_eq!(start, Some(IVec2::new(1, 0)));
+ * assert_eq!(grid.get(IVec2::new(2, 1)), Some(&true));
+ * assert_eq!(grid.get(IVec2::new(3, 1)), None);
+ * ```
+ */
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /**
+     * A grid with every cell set to `cell`.
+     */
+    pub fn new(width: usize, height: usize, cell: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![cell; width * height],
+        }
+    }
+
+    /**
+     * Parses a grid with a cell for each character of the lines, or `None`
+     * for characters which aren't allowed. Every line must be the same width.
+     * The lines are usually the whole input, but can be any of them, such as
+     * those before a blank line.
+     */
+    pub fn parse<'a>(
+        input: impl IntoIterator<Item = &'a str>,
+        mut cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, AocError> {
+        Self::parse_with_position(input, |_: U64Vec2, c| cell(c))
+    }
+
+    /**
+     * Parses a grid like [`Grid::parse`], also passing each character's
+     * position so that markers such as the start can be found while parsing.
+     * Fails when the grid doesn't fit in the position type.
+     */
+    pub fn parse_with_position<'a, P: Position>(
+        input: impl IntoIterator<Item = &'a str>,
+        mut cell: impl FnMut(P, char) -> Option<T>,
+    ) -> Result<Self, AocError> {
+        let lines = input.into_iter().collect::<Vec<_>>();
+        let width = lines.first().map_or(0, |line| line.chars().count());
+        let height = lines.len();
+        if !P::fits(width, height) {
+            return Err(AocError::new(format!(
+                "grid {width}x{height} doesn't fit in {}",
+                std::any::type_name::<P>()
+                    .rsplit("::")
+                    .next()
+                    .unwrap_or_default()
+            )));
+        }
+        let mut cells = Vec::with_capacity(width * height);
+        for (row, line) in lines.iter().enumerate() {
+            let length = line.chars().count();
+            if length != width {
+                return Err(AocError::new(format!(
+                    "expected {width} characters like the first line but found {length}"
+                ))
+                .at_line(row + 1));
+            }
+            for (column, c) in line.chars().enumerate() {
+                let value = cell(P::from_column_row(column, row), c).ok_or_else(|| {
+                    AocError::new(format!("unexpected char {c}")).at(row + 1, column + 1)
+                })?;
+                cells.push(value);
+            }
+        }
+        Ok(Self {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /**
+     * The width and height as a position just past the last cell.
+     */
+    pub fn size<P: Position>(&self) -> P {
+        P::from_column_row(self.width, self.height)
+    }
+
+    fn index_of<P: Position>(&self, pos: P) -> Option<usize> {
+        let (column, row) = pos.column_row()?;
+        (column < self.width && row < self.height).then_some(row * self.width + column)
+    }
+
+    pub fn contains<P: Position>(&self, pos: P) -> bool {
+        self.index_of(pos).is_some()
+    }
+
+    /**
+     * The cell at `pos`, or `None` when it's outside the grid. Indexing with
+     * `grid[pos]` instead panics outside the grid.
+     */
+    pub fn get<P: Position>(&self, pos: P) -> Option<&T> {
+        self.index_of(pos).map(|index| &self.cells[index])
+    }
+
+    pub fn get_mut<P: Position>(&mut self, pos: P) -> Option<&mut T> {
+        self.index_of(pos).map(|index| &mut self.cells[index])
+    }
+
+    /**
+     * Every position in the grid, row by row.
+     */
+    pub fn positions<P: Position>(&self) -> impl Iterator<Item = P> {
+        let width = self.width;
+        (0..self.height)
+            .flat_map(move |row| (0..width).map(move |column| P::from_column_row(column, row)))
+    }
+
+    /**
+     * Every cell along with its position, row by row.
+     */
+    pub fn iter<P: Position>(&self) -> impl Iterator<Item = (P, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /**
+     * The position of the first cell, row by row, which matches `predicate`.
+     */
+    pub fn position<P: Position>(&self, predicate: impl Fn(&T) -> bool) -> Option<P> {
+        self.iter()
+            .find(|(_, cell)| predicate(cell))
+            .map(|(pos, _)| pos)
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|row| self.row(row))
+    }
+
+    pub fn column(&self, column: usize) -> impl Iterator<Item = &T> {
+        assert!(column < self.width, "column {column} is outside the grid");
+        self.cells.iter().skip(column).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|column| self.column(column))
+    }
+
+    /**
+     * The positions next to `pos` in the grid, in the order of
+     * [`Direction4::ALL`]: up, right, down then left.
+     */
+    pub fn neighbors4<P: Position>(&self, pos: P) -> impl Iterator<Item = P> + '_ {
+        self.neighbors(pos, Direction4::ALL.map(Direction4::delta))
+    }
+
+    /**
+     * The positions around `pos` in the grid, including diagonals, in the
+     * order of [`Direction8::ALL`], which is clockwise from up.
+     */
+    pub fn neighbors8<P: Position>(&self, pos: P) -> impl Iterator<Item = P> + '_ {
+        self.neighbors(pos, Direction8::ALL.map(Direction8::delta))
+    }
+
+    fn neighbors<P: Position, const N: usize>(
+        &self,
+        pos: P,
+        deltas: [(i8, i8); N],
+    ) -> impl Iterator<Item = P> + '_ {
+        let center = pos.column_row().filter(|_| self.contains(pos));
+        deltas.into_iter().filter_map(move |(dx, dy)| {
+            let (column, row) = center?;
+            let column = column.checked_add_signed(dx.into())?;
+            let row = row.checked_add_signed(dy.into())?;
+            (column < self.width && row < self.height).then(|| P::from_column_row(column, row))
+        })
+    }
+
+    /**
+     * A grid of the same size with `f` applied to every cell.
+     */
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T, P: Position> Index<P> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: P) -> &T {
+        let index = self.index_of(pos).unwrap_or_else(|| {
+            panic!(
+                "{:?} is outside the {}x{} grid",
+                pos.column_row(),
+                self.width,
+                self.height
+            )
+        });
+        &self.cells[index]
+    }
+}
+
+impl<T, P: Position> IndexMut<P> for Grid<T> {
+    fn index_mut(&mut self, pos: P) -> &mut T {
+        let index = self.index_of(pos).unwrap_or_else(|| {
+            panic!(
+                "{:?} is outside the {}x{} grid",
+                pos.column_row(),
+                self.width,
+                self.height
+            )
+        });
+        &mut self.cells[index]
+    }
+}
+
+/**
+ * Writes each row of cells on its own line, which parses back into the same
+ * grid when each cell writes the character it was parsed from.
+ */
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, row) in self.rows().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+#.#.
+..#.
+#...";
+
+    fn example() -> Grid<char> {
+        Grid::parse(EXAMPLE.lines(), Some).unwrap()
+    }
+
+    #[test]
+    fn round_trip() {
+        let grid = example();
+        assert_eq!((grid.width(), grid.height()), (4, 3));
+        assert_eq!(grid.size::<IVec2>(), IVec2::new(4, 3));
+        assert_eq!(grid.to_string(), EXAMPLE);
+        assert_eq!(Grid::parse(grid.to_string().lines(), Some), Ok(grid));
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            Grid::parse("#.\n#.#".lines(), Some)
+                .unwrap_err()
+                .to_string(),
+            "line 2: expected 2 characters like the first line but found 3"
+        );
+        let error = Grid::parse("..\n.x".lines(), |c| (c == '.').then_some(c)).unwrap_err();
+        assert_eq!(error, AocError::new("unexpected char x").at(2, 2));
+        let wide = ".".repeat(300);
+        let error = Grid::parse_with_position(wide.lines(), |_: U8Vec2, c| Some(c)).unwrap_err();
+        assert_eq!(error.message, "grid 300x1 doesn't fit in U8Vec2");
+        assert_eq!(Grid::parse("".lines(), Some).map(|g| g.height()), Ok(0));
+    }
+
+    #[test]
+    fn indexing() {
+        let mut grid = example();
+        assert_eq!(grid[I16Vec2::new(2, 1)], '#');
+        assert_eq!(grid.get(I16Vec2::new(-1, 0)), None);
+        assert_eq!(grid.get(U8Vec2::new(4, 0)), None);
+        grid[UVec2::new(1, 0)] = '#';
+        assert_eq!(grid.row(0), ['#', '#', '#', '.']);
+        assert_eq!(grid.position(|&c| c == '.'), Some(IVec2::new(3, 0)));
+        assert_eq!(grid.column(0).collect::<String>(), "#.#",);
+        assert_eq!(grid.columns().count(), 4);
+        assert_eq!(
+            grid.map(|&c| c == '#')
+                .iter::<IVec2>()
+                .filter(|(_, &wall)| wall)
+                .count(),
+            5
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Some((4, 0)) is outside the 4x3 grid")]
+    fn index_outside() {
+        let _ = example()[IVec2::new(4, 0)];
+    }
+
+    #[test]
+    fn neighbors() {
+        let grid = example();
+        assert_eq!(
+            grid.neighbors4(IVec2::new(0, 0)).collect::<Vec<_>>(),
+            [IVec2::new(1, 0), IVec2::new(0, 1)]
+        );
+        assert_eq!(grid.neighbors8(U8Vec2::new(1, 1)).count(), 8);
+        assert_eq!(grid.neighbors8(U8Vec2::new(3, 2)).count(), 3);
+        assert_eq!(grid.neighbors4(IVec2::new(-1, 0)).count(), 0);
+    }
+}
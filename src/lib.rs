@@ -1,4 +1,31 @@
-use std::{collections::HashSet, str::Lines};
+pub mod answers;
+pub mod bench;
+pub mod bitset;
+pub mod constraint;
+pub mod day;
+pub mod direction;
+pub mod error;
+pub mod examples;
+pub mod format;
+pub mod graph;
+pub mod grid;
+pub mod params;
+pub mod part;
+pub mod pathfinding;
+pub mod property;
+pub mod readme;
+pub mod runner;
+pub mod scaffold;
+pub mod solution;
+pub mod trace;
+
+pub use day::Day;
+pub use error::AocError;
+pub use params::Params;
+pub use runner::Runner;
+pub use solution::Solution;
+
+use std::fmt;
 
 pub struct Named<T> {
     pub wrapped: T,
@@ -18,46 +45,170 @@ macro_rules! named {
     };
 }
 
-pub struct Runner {
-    module_name: &'static str,
-    operations: HashSet<String>,
+/**
+ * The answer to a part. Parts can return any integer, which is an
+ * [`Answer::Integer`], or string, which is [`Answer::Text`], and build the
+ * other kinds explicitly. Answers are compared by kind and value, so an
+ * integer never equals the text it formats as:
+ *
+ * ```
+ * use advent_of_code::Answer;
+ *
+ * assert_eq!(Answer::from(42u64), Answer::Integer(42));
+ * assert_eq!(Answer::from("42"), Answer::text("42"));
+ * assert_ne!(Answer::from("42"), Answer::from(42));
+ * assert_eq!(Answer::coordinates(6, 1).to_string(), "6,1");
+ * assert_eq!(Answer::picture(["#.", ".#"]).to_string(), "#.\n.#");
+ * ```
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub enum Answer {
+    Integer(i128),
+    Text(String),
+    /** A position such as `x,y`. */
+    Coordinates(i64, i64),
+    /** ASCII art spanning several lines, such as robots forming a picture. */
+    Picture(String),
 }
 
-type Operation = fn(Lines) -> String;
+impl Answer {
+    pub fn text(text: impl Into<String>) -> Self {
+        Self::Text(text.into())
+    }
+
+    pub fn coordinates(x: impl Into<i64>, y: impl Into<i64>) -> Self {
+        Self::Coordinates(x.into(), y.into())
+    }
 
-impl Runner {
-    pub fn create(module_name: &'static str) -> Self {
-        Self {
-            module_name,
-            operations: std::env::args().skip(1).collect(),
+    /**
+     * Makes a picture from its rows.
+     */
+    pub fn picture<R: fmt::Display>(rows: impl IntoIterator<Item = R>) -> Self {
+        Self::Picture(
+            rows.into_iter()
+                .map(|row| row.to_string())
+                .collect::<Vec<_>>()
+                .join("\n"),
+        )
+    }
+
+    /**
+     * The answer on a single line, for tables which can't fit a picture.
+     */
+    pub fn summary(&self) -> String {
+        match self {
+            Self::Picture(picture) => {
+                let width = picture
+                    .lines()
+                    .map(|l| l.chars().count())
+                    .max()
+                    .unwrap_or(0);
+                format!("(picture {width}x{})", picture.lines().count())
+            }
+            answer => answer.to_string(),
         }
     }
+}
 
-    pub fn run(&self, op: &Named<Operation>, input: &str) {
-        let enabled: bool = self.operations.is_empty() || self.operations.contains(op.name);
-        let result: String = if enabled {
-            (op.wrapped)(input.lines())
-        } else {
-            String::from("(DISABLED)")
-        };
-        println!("{} {}:\n{}", self.module_name, op.name, result);
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Integer(n) => write!(f, "{n}"),
+            Self::Text(text) | Self::Picture(text) => f.write_str(text),
+            Self::Coordinates(x, y) => write!(f, "{x},{y}"),
+        }
     }
 }
 
-#[macro_export]
-macro_rules! create_runner {
-    () => {
-        &Runner::create(module_path!())
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        Self::text(text)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Self::text(text)
+    }
+}
+
+macro_rules! integer_answer {
+    ( $($t:ty),+ ) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    Self::Integer(n.into())
+                }
+            }
+        )+
     };
 }
 
+integer_answer!(u8, u16, u32, u64, i8, i16, i32, i64, i128);
+
+impl From<usize> for Answer {
+    fn from(n: usize) -> Self {
+        Self::Integer(n.try_into().expect("usize fits in an i128"))
+    }
+}
+
+/**
+ * A part which parses the whole input itself. Parts are written as functions
+ * of any [`part::Part`] shape, which [`Day::part`] wraps into this.
+ */
+pub type Operation = Box<dyn Fn(&str, &Params) -> Result<Answer, AocError> + Send + Sync>;
+
+/**
+ * Asserts that a part gives the expected answer for an input, for example
+ * `verify!(part1, input, "11")` or `verify!(part1, input, 11)`, comparing
+ * them as [`Answer`]s. Other forms verify a part of a
+ * [`Solution`] with `verify!(MySolution::part1, input, "11")`, pass
+ * [`Params`] to a part, or to a solution's parse, with
+ * `verify!(part1, input, params, "11")`, or check that every
+ * variant of a part agrees with `verify!([part1, part1_fast], input, "11")`.
+ */
 #[macro_export]
 macro_rules! verify {
+    ( [ $($op:ident),+ $(,)? ], $input:ident, $expected:expr ) => {{
+        $( $crate::verify!($op, $input, $expected); )+
+    }};
+    ( [ $($op:ident),+ $(,)? ], $input:ident, $params:expr, $expected:expr ) => {{
+        $( $crate::verify!($op, $input, $params, $expected); )+
+    }};
+    ( $solution:ident :: $part:ident, $input:ident, $params:expr, $expected:expr ) => {{
+        let parsed = <$solution as $crate::Solution>::parse($input.lines(), &$params)
+            .unwrap_or_else(|e| panic!("{} {}: {e}", module_path!(), std::stringify!($solution)));
+        let result: $crate::Answer = <$solution as $crate::Solution>::$part(&parsed).into();
+        assert_eq!(
+            result,
+            $crate::Answer::from($expected),
+            "{} {}::{}",
+            module_path!(),
+            std::stringify!($solution),
+            std::stringify!($part)
+        );
+    }};
+    ( $solution:ident :: $part:ident, $input:ident, $expected:expr ) => {{
+        $crate::verify!($solution::$part, $input, $crate::Params::default(), $expected)
+    }};
+    ( $op:ident, $input:ident, $params:expr, $expected:expr ) => {{
+        let result = $crate::part::call(&$op, $input, &$params)
+            .unwrap_or_else(|e| panic!("{} {}: {e}", module_path!(), std::stringify!($op)));
+        assert_eq!(
+            result,
+            $crate::Answer::from($expected),
+            "{} {} {}",
+            module_path!(),
+            std::stringify!($op),
+            $params
+        );
+    }};
     ( $op:ident, $input:ident, $expected:expr ) => {{
-        let result = $op($input.lines());
+        let result = $crate::part::call(&$op, $input, &$crate::Params::default())
+            .unwrap_or_else(|e| panic!("{} {}: {e}", module_path!(), std::stringify!($op)));
         assert_eq!(
             result,
-            $expected,
+            $crate::Answer::from($expected),
             "{} {}",
             module_path!(),
             std::stringify!($op)
@@ -0,0 +1,151 @@
+use crate::AocError;
+use std::{fmt, str::FromStr};
+
+/**
+ * Named values which tune a part, such as how many times to blink or the
+ * size of a grid, which would otherwise have to be hardcoded or added to the
+ * puzzle input.
+ *
+ * Each part declares its parameters with their defaults, usually in a
+ * constant next to the part, and `--param name=value` overrides them:
+ *
+ * ```
+ * use advent_of_code::params::Params;
+ *
+ * const PART1_PARAMS: &[(&str, &str)] = &[("blinks", "25")];
+ *
+ * let params = Params::create(PART1_PARAMS).with("blinks", 6);
+ * assert_eq!(params.get::<usize>("blinks"), Ok(6));
+ * ```
+ */
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Params {
+    values: Vec<(String, String)>,
+}
+
+impl Params {
+    pub fn create(defaults: &[(&str, &str)]) -> Self {
+        Self {
+            values: defaults
+                .iter()
+                .map(|&(name, value)| (name.to_owned(), value.to_owned()))
+                .collect(),
+        }
+    }
+
+    /**
+     * Sets a parameter, replacing its current value.
+     */
+    pub fn with(mut self, name: &str, value: impl ToString) -> Self {
+        let value = value.to_string();
+        match self.values.iter_mut().find(|(n, _)| n == name) {
+            Some((_, existing)) => *existing = value,
+            None => self.values.push((name.to_owned(), value)),
+        }
+        self
+    }
+
+    /**
+     * Replaces the values of the parameters which are declared here, ignoring
+     * the others since they're for other parts.
+     */
+    pub fn overridden(&self, overrides: &[(String, String)]) -> Self {
+        overrides
+            .iter()
+            .filter(|(name, _)| self.declares(name))
+            .fold(self.clone(), |params, (name, value)| {
+                params.with(name, value)
+            })
+    }
+
+    pub fn declares(&self, name: &str) -> bool {
+        self.values.iter().any(|(n, _)| n == name)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /**
+     * Parses the value of a parameter, which is an error when the part
+     * doesn't declare it or its value doesn't parse.
+     */
+    pub fn get<T>(&self, name: &str) -> Result<T, AocError>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        let (_, value) = self
+            .values
+            .iter()
+            .find(|(n, _)| n == name)
+            .ok_or_else(|| AocError::new(format!("no parameter named {name}")))?;
+        value
+            .parse()
+            .map_err(|e| AocError::new(format!("parameter {name}={value}: {e}")))
+    }
+}
+
+impl fmt::Display for Params {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let values = self
+            .values
+            .iter()
+            .map(|(name, value)| format!("{name}={value}"))
+            .collect::<Vec<_>>();
+        f.write_str(&values.join(" "))
+    }
+}
+
+/**
+ * Parses the argument of `--param`, which looks like `name=value`.
+ */
+pub fn parse_assignment(arg: &str) -> Result<(String, String), String> {
+    match arg.split_once('=') {
+        Some((name, value)) if !name.is_empty() => Ok((name.to_owned(), value.to_owned())),
+        _ => Err(format!("expected --param name=value but found {arg}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEFAULTS: &[(&str, &str)] = &[("blinks", "25"), ("size", "101,103")];
+
+    #[test]
+    fn get() {
+        let params = Params::create(DEFAULTS);
+        assert_eq!(params.get::<usize>("blinks"), Ok(25));
+        assert_eq!(params.get::<String>("size"), Ok("101,103".to_owned()));
+        assert_eq!(
+            params.get::<usize>("size").unwrap_err().message,
+            "parameter size=101,103: invalid digit found in string"
+        );
+        assert_eq!(
+            params.get::<usize>("robots").unwrap_err().message,
+            "no parameter named robots"
+        );
+    }
+
+    #[test]
+    fn overridden() {
+        let overrides = [
+            ("blinks".to_owned(), "40".to_owned()),
+            ("robots".to_owned(), "3".to_owned()),
+        ];
+        let params = Params::create(DEFAULTS).overridden(&overrides);
+        assert_eq!(params.to_string(), "blinks=40 size=101,103");
+        assert!(!params.declares("robots"));
+    }
+
+    #[test]
+    fn assignment() {
+        assert_eq!(
+            parse_assignment("blinks=40"),
+            Ok(("blinks".to_owned(), "40".to_owned()))
+        );
+        assert!(parse_assignment("blinks").is_err());
+        assert!(parse_assignment("=40").is_err());
+    }
+}
@@ -0,0 +1,116 @@
+use crate::{error::Outcome, Answer, AocError, Params};
+use std::str::Lines;
+
+/**
+ * A function which solves a part, in any of the shapes parts are written in.
+ * Parts receive the input as either `Lines`, the whole `&str`, or its bytes as
+ * `&[u8]`, optionally followed by `&Params`, and return any [`Outcome`]:
+ *
+ * ```ignore
+ * fn part1(input: Lines) -> String
+ * fn part1(input: &str) -> Result<u64, AocError>
+ * fn part2(input: &[u8], params: &Params) -> Result<String, AocError>
+ * ```
+ *
+ * `Args` only tells the shapes apart, and is inferred from the function.
+ */
+pub trait Part<Args>: Copy + Send + Sync + 'static {
+    fn call(&self, input: &str, params: &Params) -> Result<Answer, AocError>;
+}
+
+impl<F, R> Part<(Lines<'static>,)> for F
+where
+    F: Fn(Lines) -> R + Copy + Send + Sync + 'static,
+    R: Outcome,
+{
+    fn call(&self, input: &str, _: &Params) -> Result<Answer, AocError> {
+        self(input.lines()).into_result()
+    }
+}
+
+impl<F, R> Part<(&'static str,)> for F
+where
+    F: Fn(&str) -> R + Copy + Send + Sync + 'static,
+    R: Outcome,
+{
+    fn call(&self, input: &str, _: &Params) -> Result<Answer, AocError> {
+        self(input).into_result()
+    }
+}
+
+impl<F, R> Part<(&'static [u8],)> for F
+where
+    F: Fn(&[u8]) -> R + Copy + Send + Sync + 'static,
+    R: Outcome,
+{
+    fn call(&self, input: &str, _: &Params) -> Result<Answer, AocError> {
+        self(input.as_bytes()).into_result()
+    }
+}
+
+impl<F, R> Part<(Lines<'static>, Params)> for F
+where
+    F: Fn(Lines, &Params) -> R + Copy + Send + Sync + 'static,
+    R: Outcome,
+{
+    fn call(&self, input: &str, params: &Params) -> Result<Answer, AocError> {
+        self(input.lines(), params).into_result()
+    }
+}
+
+impl<F, R> Part<(&'static str, Params)> for F
+where
+    F: Fn(&str, &Params) -> R + Copy + Send + Sync + 'static,
+    R: Outcome,
+{
+    fn call(&self, input: &str, params: &Params) -> Result<Answer, AocError> {
+        self(input, params).into_result()
+    }
+}
+
+impl<F, R> Part<(&'static [u8], Params)> for F
+where
+    F: Fn(&[u8], &Params) -> R + Copy + Send + Sync + 'static,
+    R: Outcome,
+{
+    fn call(&self, input: &str, params: &Params) -> Result<Answer, AocError> {
+        self(input.as_bytes(), params).into_result()
+    }
+}
+
+/**
+ * Runs a part of any shape, for [`crate::verify!`].
+ */
+pub fn call<Args, P: Part<Args>>(
+    part: &P,
+    input: &str,
+    params: &Params,
+) -> Result<Answer, AocError> {
+    part.call(input, params)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(input: Lines) -> String {
+        input.count().to_string()
+    }
+
+    fn text(input: &str) -> Result<usize, AocError> {
+        Ok(input.len())
+    }
+
+    fn bytes(input: &[u8], params: &Params) -> Result<String, AocError> {
+        let byte = params.get::<u8>("byte")?;
+        Ok(input.iter().filter(|&&b| b == byte).count().to_string())
+    }
+
+    #[test]
+    fn shapes() {
+        let params = Params::create(&[("byte", "10")]);
+        assert_eq!(call(&lines, "a\nb\n", &params), Ok(Answer::text("2")));
+        assert_eq!(call(&text, "a\nb\n", &params), Ok(Answer::Integer(4)));
+        assert_eq!(call(&bytes, "a\nb\n", &params), Ok(Answer::text("2")));
+    }
+}
@@ -0,0 +1,548 @@
+use crate::{
+    direction::Direction4,
+    grid::{Grid, Position},
+};
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+/**
+ * The cheapest cost of reaching every state a search visited, along with
+ * every state it can be reached from at that cost. Those predecessors form a
+ * DAG of all the cheapest paths, so besides following one path back to the
+ * start, it can find every state on any cheapest path. Steps which cost
+ * nothing can make states of equal cost each other's predecessors, but the
+ * start never has any, so following them always ends there:
+ *
+ * ```
+ * use advent_of_code::pathfinding::bfs;
+ *
+ * // a diamond, where 0 reaches 3 through either 1 or 2
+ * let paths = bfs(0, |&n: &u8| match n {
+ *     0 => vec![1, 2],
+ *     1 | 2 => vec![3],
+ *     _ => vec![],
+ * });
+ * assert_eq!(paths.cost(&3), Some(2));
+ * assert_eq!(paths.path_to(&3), Some(vec![0, 1, 3]));
+ * assert_eq!(paths.on_paths_to([3]).len(), 4);
+ * ```
+ */
+#[derive(Clone, Debug)]
+pub struct Paths<S, C> {
+    start: S,
+    costs: HashMap<S, C>,
+    predecessors: HashMap<S, Vec<S>>,
+}
+
+impl<S: Clone + Eq + Hash, C: Copy + Ord + Add<Output = C> + Default> Paths<S, C> {
+    fn new(start: S) -> Self {
+        Self {
+            costs: HashMap::from([(start.clone(), C::default())]),
+            start,
+            predecessors: HashMap::new(),
+        }
+    }
+
+    /**
+     * Records reaching `next` from `state` at `cost`, returning whether that
+     * is the cheapest way to reach it so far, so it needs exploring again.
+     */
+    fn reach(&mut self, state: &S, next: &S, cost: C) -> bool {
+        if *next == self.start {
+            // nothing is cheaper than the start, even with steps costing nothing
+            return false;
+        }
+        match self.costs.get(next) {
+            Some(&old) if cost > old => false,
+            Some(&old) if cost == old => {
+                let predecessors = self.predecessors.entry(next.clone()).or_default();
+                if !predecessors.contains(state) {
+                    predecessors.push(state.clone());
+                }
+                false
+            }
+            _ => {
+                self.costs.insert(next.clone(), cost);
+                self.predecessors.insert(next.clone(), vec![state.clone()]);
+                true
+            }
+        }
+    }
+
+    /**
+     * The cheapest cost of reaching `state`, or `None` if it can't be reached.
+     */
+    pub fn cost(&self, state: &S) -> Option<C> {
+        self.costs.get(state).copied()
+    }
+
+    /**
+     * Every state which was reached, with its cheapest cost.
+     */
+    pub fn costs(&self) -> impl Iterator<Item = (&S, C)> {
+        self.costs.iter().map(|(state, &cost)| (state, cost))
+    }
+
+    /**
+     * The states which reach `state` on one of its cheapest paths, which is
+     * empty for the start.
+     */
+    pub fn predecessors(&self, state: &S) -> &[S] {
+        self.predecessors.get(state).map_or(&[], Vec::as_slice)
+    }
+
+    /**
+     * The lowest cost of any goal state, along with every goal state which
+     * is reached at that cost.
+     */
+    pub fn cheapest(&self, mut is_goal: impl FnMut(&S) -> bool) -> Option<(C, Vec<S>)> {
+        let cost = self
+            .costs()
+            .filter(|(state, _)| is_goal(state))
+            .map(|(_, cost)| cost)
+            .min()?;
+        let goals = self
+            .costs()
+            .filter(|&(state, c)| c == cost && is_goal(state))
+            .map(|(state, _)| state.clone())
+            .collect();
+        Some((cost, goals))
+    }
+
+    /**
+     * One of the cheapest paths from the start to `state`, including both,
+     * or `None` if it can't be reached.
+     */
+    pub fn path_to(&self, state: &S) -> Option<Vec<S>> {
+        self.costs.get(state)?;
+        let mut path = vec![state.clone()];
+        while let Some(previous) = self.predecessors(path.last()?).first() {
+            debug_assert!(path.len() <= self.costs.len(), "predecessors form a cycle");
+            path.push(previous.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /**
+     * Every state on any of the cheapest paths from the start to one of the
+     * `goals`, including the start and the goals themselves.
+     */
+    pub fn on_paths_to(&self, goals: impl IntoIterator<Item = S>) -> HashSet<S> {
+        let mut visited = HashSet::new();
+        let mut traverse = goals
+            .into_iter()
+            .filter(|goal| self.costs.contains_key(goal))
+            .collect::<Vec<_>>();
+        while let Some(state) = traverse.pop() {
+            if !visited.contains(&state) {
+                traverse.extend(self.predecessors(&state).iter().cloned());
+                visited.insert(state);
+            }
+        }
+        visited
+    }
+}
+
+/**
+ * Where a search keeps the cheapest cost of each state it reaches, without
+ * the predecessors of [`Paths`]. A `HashMap` works for any state, while a
+ * [`Grid`] of costs indexed by position, or by position and direction,
+ * avoids hashing for searches over a map:
+ *
+ * ```
+ * use advent_of_code::{grid::Grid, pathfinding::bfs_costs};
+ * use glam::U8Vec2;
+ *
+ * let open = Grid::parse("..#\n...".lines(), |c| Some(c == '.')).unwrap();
+ * let steps = bfs_costs(U8Vec2::ZERO, Grid::new(3, 2, None), |&pos| {
+ *     open.neighbors4(pos).filter(|&next| open[next])
+ * });
+ * assert_eq!(steps[U8Vec2::new(2, 1)], Some(3));
+ * assert_eq!(steps[U8Vec2::new(2, 0)], None);
+ * ```
+ */
+pub trait Costs<S, C> {
+    fn cost(&self, state: &S) -> Option<C>;
+
+    fn set_cost(&mut self, state: &S, cost: C);
+}
+
+impl<S: Clone + Eq + Hash, C: Copy> Costs<S, C> for HashMap<S, C> {
+    fn cost(&self, state: &S) -> Option<C> {
+        self.get(state).copied()
+    }
+
+    fn set_cost(&mut self, state: &S, cost: C) {
+        self.insert(state.clone(), cost);
+    }
+}
+
+/**
+ * Panics when a state outside the grid is reached.
+ */
+impl<P: Position, C: Copy> Costs<P, C> for Grid<Option<C>> {
+    fn cost(&self, &pos: &P) -> Option<C> {
+        self.get(pos).copied().flatten()
+    }
+
+    fn set_cost(&mut self, &pos: &P, cost: C) {
+        self[pos] = Some(cost);
+    }
+}
+
+/**
+ * Panics when a state outside the grid is reached.
+ */
+impl<P: Position, C: Copy> Costs<(P, Direction4), C> for Grid<[Option<C>; 4]> {
+    fn cost(&self, &(pos, dir): &(P, Direction4)) -> Option<C> {
+        self.get(pos).and_then(|costs| costs[dir.index()])
+    }
+
+    fn set_cost(&mut self, &(pos, dir): &(P, Direction4), cost: C) {
+        self[pos][dir.index()] = Some(cost);
+    }
+}
+
+/**
+ * Finds the fewest steps from `start` to every state it can reach, where
+ * `successors` lists the states one step away from a state.
+ */
+pub fn bfs<S, I>(start: S, mut successors: impl FnMut(&S) -> I) -> Paths<S, usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut paths = Paths::new(start.clone());
+    let mut traverse = VecDeque::from([(start, 0)]);
+    while let Some((state, steps)) = traverse.pop_front() {
+        for next in successors(&state) {
+            if paths.reach(&state, &next, steps + 1) {
+                traverse.push_back((next, steps + 1));
+            }
+        }
+    }
+    paths
+}
+
+/**
+ * Like [`bfs`], but only records the fewest steps to each state, in `costs`.
+ */
+pub fn bfs_costs<S, M, I>(start: S, mut costs: M, successors: impl FnMut(&S) -> I) -> M
+where
+    S: PartialEq,
+    M: Costs<S, usize>,
+    I: IntoIterator<Item = S>,
+{
+    breadth_first(start, None, &mut costs, successors);
+    costs
+}
+
+/**
+ * The fewest steps from `start` to `goal`, or `None` if it can't be reached,
+ * searching only until the goal is found. `costs` is where the steps to the
+ * states on the way are kept, as for [`bfs_costs`].
+ */
+pub fn bfs_to<S, M, I>(
+    start: S,
+    goal: &S,
+    mut costs: M,
+    successors: impl FnMut(&S) -> I,
+) -> Option<usize>
+where
+    S: PartialEq,
+    M: Costs<S, usize>,
+    I: IntoIterator<Item = S>,
+{
+    breadth_first(start, Some(goal), &mut costs, successors)
+}
+
+fn breadth_first<S, M, I>(
+    start: S,
+    goal: Option<&S>,
+    costs: &mut M,
+    mut successors: impl FnMut(&S) -> I,
+) -> Option<usize>
+where
+    S: PartialEq,
+    M: Costs<S, usize>,
+    I: IntoIterator<Item = S>,
+{
+    costs.set_cost(&start, 0);
+    let mut traverse = VecDeque::from([(start, 0)]);
+    while let Some((state, steps)) = traverse.pop_front() {
+        if goal == Some(&state) {
+            return Some(steps);
+        }
+        for next in successors(&state) {
+            if costs.cost(&next).is_none() {
+                costs.set_cost(&next, steps + 1);
+                traverse.push_back((next, steps + 1));
+            }
+        }
+    }
+    None
+}
+
+/**
+ * Finds the cheapest cost from `start` to every state it can reach, where
+ * `successors` lists the states a step away from a state along with the cost
+ * of that step, which mustn't be negative.
+ */
+pub fn dijkstra<S, C, I>(start: S, successors: impl FnMut(&S) -> I) -> Paths<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (S, C)>,
+{
+    search(start, successors, |_| C::default(), |_| false).0
+}
+
+/**
+ * Like [`dijkstra`], but only records the cheapest cost of each state, in
+ * `costs`.
+ */
+pub fn dijkstra_costs<S, C, M, I>(start: S, mut costs: M, mut successors: impl FnMut(&S) -> I) -> M
+where
+    S: Clone,
+    C: Copy + Ord + Add<Output = C> + Default,
+    M: Costs<S, C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    costs.set_cost(&start, C::default());
+    // as in `search`, the heap refers to states by their index
+    let mut states = vec![start];
+    let mut heap = BinaryHeap::from([Reverse((C::default(), 0))]);
+    while let Some(Reverse((cost, index))) = heap.pop() {
+        let state = states[index].clone();
+        if costs.cost(&state).is_some_and(|best| best < cost) {
+            continue;
+        }
+        for (next, step) in successors(&state) {
+            let next_cost = cost + step;
+            if costs.cost(&next).is_none_or(|best| next_cost < best) {
+                costs.set_cost(&next, next_cost);
+                heap.push(Reverse((next_cost, states.len())));
+                states.push(next);
+            }
+        }
+    }
+    costs
+}
+
+/**
+ * Finds the cheapest path from `start` to a state which `is_goal`, returning
+ * its cost and every state along it, or `None` if no goal can be reached.
+ * `successors` works as for [`dijkstra`], and `heuristic` guesses the cost
+ * from a state to the nearest goal, which must never be more than the real
+ * cost for the path to be the cheapest.
+ */
+pub fn astar<S, C, I>(
+    start: S,
+    successors: impl FnMut(&S) -> I,
+    heuristic: impl FnMut(&S) -> C,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<(C, Vec<S>)>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let (paths, goal) = search(start, successors, heuristic, is_goal);
+    let goal = goal?;
+    Some((paths.cost(&goal)?, paths.path_to(&goal)?))
+}
+
+/**
+ * Explores states in order of their cost plus `heuristic`, until one which
+ * `is_goal`, or until every reachable state has been explored.
+ */
+fn search<S, C, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> (Paths<S, C>, Option<S>)
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut paths = Paths::new(start.clone());
+    // the heap refers to states by their index here, so they needn't be `Ord`
+    let mut states = vec![start];
+    let mut heap = BinaryHeap::from([Reverse((heuristic(&states[0]), C::default(), 0))]);
+    while let Some(Reverse((_, cost, index))) = heap.pop() {
+        let state = states[index].clone();
+        if paths.cost(&state).is_some_and(|best| best < cost) {
+            // already explored more cheaply
+            continue;
+        }
+        if is_goal(&state) {
+            return (paths, Some(state));
+        }
+        for (next, step) in successors(&state) {
+            let next_cost = cost + step;
+            if paths.reach(&state, &next, next_cost) {
+                heap.push(Reverse((
+                    next_cost + heuristic(&next),
+                    next_cost,
+                    states.len(),
+                )));
+                states.push(next);
+            }
+        }
+    }
+    (paths, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{direction::Direction4, grid::Grid};
+    use glam::I8Vec2;
+
+    const MAZE: &str = "\
+.....
+.###.
+...#.
+.#...";
+
+    fn open_neighbors(maze: &Grid<bool>, pos: I8Vec2) -> impl Iterator<Item = I8Vec2> + '_ {
+        maze.neighbors4(pos).filter(|&next| maze[next])
+    }
+
+    #[test]
+    fn bfs_maze() {
+        let maze = Grid::parse(MAZE.lines(), |c| Some(c == '.')).unwrap();
+        let paths = bfs(I8Vec2::ZERO, |&pos| open_neighbors(&maze, pos));
+        let end = I8Vec2::new(4, 3);
+        assert_eq!(paths.cost(&end), Some(7));
+        assert_eq!(paths.path_to(&end).map(|path| path.len()), Some(8));
+        assert_eq!(paths.cost(&I8Vec2::new(2, 1)), None);
+        assert_eq!(paths.path_to(&I8Vec2::new(2, 1)), None);
+        // both ways around the wall are cheapest
+        assert_eq!(paths.on_paths_to([end]).len(), 14);
+        assert_eq!(paths.predecessors(&I8Vec2::ZERO), &[]);
+    }
+
+    #[test]
+    fn dijkstra_free_steps() {
+        let paths = dijkstra(0, |&n: &u8| match n {
+            0 => vec![(1, 0)],
+            1 => vec![(0, 0), (2, 1), (3, 0)],
+            3 => vec![(1, 0)],
+            _ => vec![],
+        });
+        assert_eq!(paths.predecessors(&0), &[]);
+        assert_eq!(paths.path_to(&2), Some(vec![0, 1, 2]));
+        assert_eq!(paths.path_to(&3), Some(vec![0, 1, 3]));
+        // going round 1 and 3 costs nothing, so 3 is on a cheapest path too
+        assert_eq!(paths.on_paths_to([2]), HashSet::from([0, 1, 2, 3]));
+    }
+
+    #[test]
+    fn bfs_grid_costs() {
+        let maze = Grid::parse(MAZE.lines(), |c| Some(c == '.')).unwrap();
+        let end = I8Vec2::new(4, 3);
+        let empty = || Grid::new(maze.width(), maze.height(), None);
+        let steps = bfs_costs(I8Vec2::ZERO, empty(), |&pos| open_neighbors(&maze, pos));
+        let paths = bfs(I8Vec2::ZERO, |&pos| open_neighbors(&maze, pos));
+        for pos in maze.positions::<I8Vec2>() {
+            assert_eq!(steps.cost(&pos), paths.cost(&pos), "{pos}");
+        }
+        assert_eq!(
+            bfs_to(I8Vec2::ZERO, &end, empty(), |&pos| open_neighbors(
+                &maze, pos
+            )),
+            Some(7)
+        );
+        assert_eq!(
+            bfs_to(I8Vec2::ZERO, &I8Vec2::new(2, 1), HashMap::new(), |&pos| {
+                open_neighbors(&maze, pos)
+            }),
+            None
+        );
+    }
+
+    #[test]
+    fn dijkstra_turns() {
+        // turning costs more than moving, so the path with one turn wins
+        let maze = Grid::parse(MAZE.lines(), |c| Some(c == '.')).unwrap();
+        let paths = dijkstra((I8Vec2::ZERO, Direction4::East), |&(pos, dir)| {
+            let forward = pos + dir.offset::<I8Vec2>();
+            [
+                (forward, dir, 1),
+                (pos, dir.clockwise(), 10),
+                (pos, dir.counter_clockwise(), 10),
+            ]
+            .into_iter()
+            .filter(|&(pos, _, _)| maze.get(pos) == Some(&true))
+            .map(|(pos, dir, cost)| ((pos, dir), cost))
+            .collect::<Vec<_>>()
+        });
+        let (cost, goals) = paths
+            .cheapest(|&(pos, _)| pos == I8Vec2::new(4, 3))
+            .unwrap();
+        assert_eq!(
+            (cost, goals),
+            (17, vec![(I8Vec2::new(4, 3), Direction4::South)])
+        );
+    }
+
+    #[test]
+    fn dijkstra_grid_costs() {
+        let maze = Grid::parse(MAZE.lines(), |c| Some(c == '.')).unwrap();
+        let moves = |&(pos, dir): &(I8Vec2, Direction4)| {
+            let forward = pos + dir.offset::<I8Vec2>();
+            let mut moves = vec![
+                ((pos, dir.clockwise()), 10),
+                ((pos, dir.counter_clockwise()), 10),
+            ];
+            if maze.get(forward) == Some(&true) {
+                moves.push(((forward, dir), 1));
+            }
+            moves
+        };
+        let start = (I8Vec2::ZERO, Direction4::East);
+        let costs = dijkstra_costs(
+            start,
+            Grid::new(maze.width(), maze.height(), [None; 4]),
+            moves,
+        );
+        let paths = dijkstra(start, moves);
+        assert_eq!(
+            costs.cost(&(I8Vec2::new(4, 3), Direction4::South)),
+            Some(17)
+        );
+        for pos in maze.positions::<I8Vec2>() {
+            for dir in Direction4::ALL {
+                assert_eq!(costs.cost(&(pos, dir)), paths.cost(&(pos, dir)));
+            }
+        }
+    }
+
+    #[test]
+    fn astar_maze() {
+        let maze = Grid::parse(MAZE.lines(), |c| Some(c == '.')).unwrap();
+        let end = I8Vec2::new(4, 3);
+        let (cost, path) = astar(
+            I8Vec2::ZERO,
+            |&pos| open_neighbors(&maze, pos).map(|next| (next, 1)),
+            |pos| (end - *pos).abs().element_sum(),
+            |&pos| pos == end,
+        )
+        .unwrap();
+        assert_eq!(cost, 7);
+        assert_eq!(path.first(), Some(&I8Vec2::ZERO));
+        assert_eq!(path.last(), Some(&end));
+        assert!(path
+            .windows(2)
+            .all(|step| (step[1] - step[0]).abs().element_sum() == 1));
+        assert_eq!(astar(I8Vec2::ZERO, |_| [], |_| 0, |&pos| pos == end), None);
+    }
+}
@@ -0,0 +1,577 @@
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::{
+    fmt::{self, Debug, Display},
+    ops::RangeInclusive,
+};
+
+/**
+ * Generates random values of some shape for property tests, and suggests
+ * simpler values to replace a failing one with, so that a failure can be
+ * reported with a minimal counterexample.
+ */
+pub trait Generator {
+    type Value: Clone + Debug;
+
+    fn generate(&self, rng: &mut StdRng) -> Self::Value;
+
+    /**
+     * Values which are a little simpler than `value`, simplest first.
+     * Shrinking stops when none of them fail.
+     */
+    fn shrink(&self, value: &Self::Value) -> Vec<Self::Value>;
+}
+
+/**
+ * Integers in a range, which shrink towards 0, or the end of the range
+ * nearest to it.
+ */
+pub struct Int {
+    range: RangeInclusive<i64>,
+}
+
+pub fn int(range: RangeInclusive<i64>) -> Int {
+    Int { range }
+}
+
+impl Generator for Int {
+    type Value = i64;
+
+    fn generate(&self, rng: &mut StdRng) -> i64 {
+        rng.gen_range(self.range.clone())
+    }
+
+    fn shrink(&self, &value: &i64) -> Vec<i64> {
+        let target = 0.clamp(*self.range.start(), *self.range.end());
+        let mut shrunk = Vec::new();
+        for n in [
+            target,
+            value - (value - target) / 2,
+            value - (value - target).signum(),
+        ] {
+            if n != value && !shrunk.contains(&n) {
+                shrunk.push(n);
+            }
+        }
+        shrunk
+    }
+}
+
+/**
+ * Lists of values from another generator, which shrink by removing elements
+ * and by shrinking the elements.
+ */
+pub struct VecOf<G> {
+    element: G,
+    len: RangeInclusive<usize>,
+}
+
+pub fn vec_of<G: Generator>(element: G, len: RangeInclusive<usize>) -> VecOf<G> {
+    VecOf { element, len }
+}
+
+/**
+ * Lists of numbers in `values`.
+ */
+pub fn numbers(len: RangeInclusive<usize>, values: RangeInclusive<i64>) -> VecOf<Int> {
+    vec_of(int(values), len)
+}
+
+impl<G: Generator> Generator for VecOf<G> {
+    type Value = Vec<G::Value>;
+
+    fn generate(&self, rng: &mut StdRng) -> Self::Value {
+        let len = rng.gen_range(self.len.clone());
+        (0..len).map(|_| self.element.generate(rng)).collect()
+    }
+
+    fn shrink(&self, value: &Self::Value) -> Vec<Self::Value> {
+        let mut shrunk = Vec::new();
+        let min_len = *self.len.start();
+        if value.len() > min_len {
+            let half = (value.len() / 2).max(min_len);
+            if half < value.len() - 1 {
+                shrunk.push(value[..half].to_vec());
+            }
+            for index in 0..value.len() {
+                let mut smaller = value.clone();
+                smaller.remove(index);
+                shrunk.push(smaller);
+            }
+        }
+        for (index, element) in value.iter().enumerate() {
+            for simpler in self.element.shrink(element) {
+                let mut simplified = value.clone();
+                simplified[index] = simpler;
+                shrunk.push(simplified);
+            }
+        }
+        shrunk
+    }
+}
+
+macro_rules! tuple_generator {
+    ( $( $name:ident $index:tt ),+ ) => {
+        impl<$( $name: Generator ),+> Generator for ( $( $name, )+ ) {
+            type Value = ( $( $name::Value, )+ );
+
+            fn generate(&self, rng: &mut StdRng) -> Self::Value {
+                ( $( self.$index.generate(rng), )+ )
+            }
+
+            fn shrink(&self, value: &Self::Value) -> Vec<Self::Value> {
+                let mut shrunk = Vec::new();
+                $(
+                    for simpler in self.$index.shrink(&value.$index) {
+                        let mut simplified = value.clone();
+                        simplified.$index = simpler;
+                        shrunk.push(simplified);
+                    }
+                )+
+                shrunk
+            }
+        }
+    };
+}
+
+tuple_generator!(A 0, B 1);
+tuple_generator!(A 0, B 1, C 2);
+tuple_generator!(A 0, B 1, C 2, D 3);
+
+/**
+ * Inputs made of blocks separated by blank lines, such as the machines of
+ * day 13, where `render` formats each generated block as its lines of text.
+ * They shrink by removing blocks and by shrinking the blocks.
+ */
+pub struct Blocks<G, F> {
+    blocks: VecOf<G>,
+    render: F,
+}
+
+pub fn blocks<G, F>(block: G, count: RangeInclusive<usize>, render: F) -> Blocks<G, F>
+where
+    G: Generator,
+    F: Fn(&G::Value) -> String,
+{
+    Blocks {
+        blocks: vec_of(block, count),
+        render,
+    }
+}
+
+/**
+ * Generated blocks along with the input text they were rendered as.
+ */
+#[derive(Clone, Debug)]
+pub struct BlockInput<T> {
+    pub blocks: Vec<T>,
+    pub text: String,
+}
+
+impl<G: Generator, F: Fn(&G::Value) -> String> Blocks<G, F> {
+    fn input(&self, blocks: Vec<G::Value>) -> BlockInput<G::Value> {
+        let text = blocks
+            .iter()
+            .map(&self.render)
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        BlockInput { blocks, text }
+    }
+}
+
+impl<G: Generator, F: Fn(&G::Value) -> String> Generator for Blocks<G, F> {
+    type Value = BlockInput<G::Value>;
+
+    fn generate(&self, rng: &mut StdRng) -> Self::Value {
+        self.input(self.blocks.generate(rng))
+    }
+
+    fn shrink(&self, value: &Self::Value) -> Vec<Self::Value> {
+        self.blocks
+            .shrink(&value.blocks)
+            .into_iter()
+            .map(|blocks| self.input(blocks))
+            .collect()
+    }
+}
+
+/**
+ * Rectangular grids of characters from `cells`, which shrink by removing
+ * rows and columns and by replacing cells with the first of `cells`, so that
+ * should be the simplest, such as `.` for an empty space.
+ */
+pub struct Grid {
+    width: RangeInclusive<usize>,
+    height: RangeInclusive<usize>,
+    cells: &'static [char],
+}
+
+pub fn grid(
+    width: RangeInclusive<usize>,
+    height: RangeInclusive<usize>,
+    cells: &'static [char],
+) -> Grid {
+    Grid {
+        width,
+        height,
+        cells,
+    }
+}
+
+/**
+ * Formats a generated grid as the lines of an input.
+ */
+pub fn grid_text(grid: &[Vec<char>]) -> String {
+    grid.iter()
+        .map(|row| row.iter().collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+impl Generator for Grid {
+    type Value = Vec<Vec<char>>;
+
+    fn generate(&self, rng: &mut StdRng) -> Self::Value {
+        let width = rng.gen_range(self.width.clone());
+        let height = rng.gen_range(self.height.clone());
+        (0..height)
+            .map(|_| {
+                (0..width)
+                    .map(|_| self.cells[rng.gen_range(0..self.cells.len())])
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn shrink(&self, value: &Self::Value) -> Vec<Self::Value> {
+        let mut shrunk = Vec::new();
+        if value.len() > *self.height.start() {
+            for y in 0..value.len() {
+                let mut smaller = value.clone();
+                smaller.remove(y);
+                shrunk.push(smaller);
+            }
+        }
+        let width = value.first().map_or(0, Vec::len);
+        if width > *self.width.start() {
+            for x in 0..width {
+                let mut narrower = value.clone();
+                narrower.iter_mut().for_each(|row| {
+                    row.remove(x);
+                });
+                shrunk.push(narrower);
+            }
+        }
+        let simplest = self.cells[0];
+        for (y, row) in value.iter().enumerate() {
+            for (x, &cell) in row.iter().enumerate() {
+                if cell != simplest {
+                    let mut simplified = value.clone();
+                    simplified[y][x] = simplest;
+                    shrunk.push(simplified);
+                }
+            }
+        }
+        shrunk
+    }
+}
+
+/**
+ * An undirected graph with nodes numbered from 0, and each edge going from
+ * the lower numbered node to the higher.
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct UndirectedGraph {
+    pub nodes: usize,
+    pub edges: Vec<(usize, usize)>,
+}
+
+/**
+ * Undirected graphs where each possible edge exists with probability
+ * `edge_chance`, which shrink by removing nodes and edges.
+ */
+pub struct Graph {
+    nodes: RangeInclusive<usize>,
+    edge_chance: f64,
+}
+
+pub fn graph(nodes: RangeInclusive<usize>, edge_chance: f64) -> Graph {
+    Graph { nodes, edge_chance }
+}
+
+impl Generator for Graph {
+    type Value = UndirectedGraph;
+
+    fn generate(&self, rng: &mut StdRng) -> UndirectedGraph {
+        let nodes = rng.gen_range(self.nodes.clone());
+        let edges = (0..nodes)
+            .flat_map(|a| (a + 1..nodes).map(move |b| (a, b)))
+            .filter(|_| rng.gen_bool(self.edge_chance))
+            .collect();
+        UndirectedGraph { nodes, edges }
+    }
+
+    fn shrink(&self, value: &UndirectedGraph) -> Vec<UndirectedGraph> {
+        let mut shrunk = Vec::new();
+        if value.nodes > *self.nodes.start() {
+            // Removing a node renumbers the ones after it to keep them contiguous.
+            let renumber = |node: usize, removed: usize| node - usize::from(node > removed);
+            for removed in 0..value.nodes {
+                shrunk.push(UndirectedGraph {
+                    nodes: value.nodes - 1,
+                    edges: value
+                        .edges
+                        .iter()
+                        .filter(|&&(a, b)| a != removed && b != removed)
+                        .map(|&(a, b)| (renumber(a, removed), renumber(b, removed)))
+                        .collect(),
+                });
+            }
+        }
+        for index in 0..value.edges.len() {
+            let mut simplified = value.clone();
+            simplified.edges.remove(index);
+            shrunk.push(simplified);
+        }
+        shrunk
+    }
+}
+
+/**
+ * What a property returns: either whether it holds, or a result whose error
+ * explains why it doesn't.
+ */
+pub trait Verdict {
+    fn failure(self) -> Option<String>;
+}
+
+impl Verdict for bool {
+    fn failure(self) -> Option<String> {
+        (!self).then(|| "property doesn't hold".to_owned())
+    }
+}
+
+impl<E: Display> Verdict for Result<(), E> {
+    fn failure(self) -> Option<String> {
+        self.err().map(|e| e.to_string())
+    }
+}
+
+/**
+ * A verdict that two values are equal, like `assert_eq!`.
+ */
+pub fn equal<T: PartialEq + Debug>(left: T, right: T) -> Result<(), String> {
+    if left == right {
+        Ok(())
+    } else {
+        Err(format!("{left:?} != {right:?}"))
+    }
+}
+
+/**
+ * Overrides the seed of every property, to explore different cases.
+ */
+pub const SEED_VAR: &str = "AOC_PROPERTY_SEED";
+
+const DEFAULT_CASES: usize = 100;
+const MAX_SHRINKS: usize = 1000;
+
+/**
+ * A failing case, both as generated and shrunk as far as it would go.
+ */
+#[derive(Debug)]
+pub struct Counterexample<T> {
+    pub seed: u64,
+    pub case: usize,
+    pub original: T,
+    pub shrunk: T,
+    pub shrinks: usize,
+    pub message: String,
+}
+
+impl<T: Debug> Display for Counterexample<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "property failed on case {} with seed {}: {}",
+            self.case, self.seed, self.message
+        )?;
+        writeln!(
+            f,
+            "minimal case after {} shrinks: {:?}",
+            self.shrinks, self.shrunk
+        )?;
+        write!(f, "original case: {:?}", self.original)
+    }
+}
+
+/**
+ * Checks that a property holds for many generated values. Runs are seeded,
+ * so they're the same every time unless [`SEED_VAR`] is set:
+ *
+ * ```
+ * use advent_of_code::property::{numbers, Property};
+ *
+ * Property::create(numbers(0..=10, -5..=5))
+ *     .cases(50)
+ *     .check(|list| list.iter().rev().rev().eq(list.iter()));
+ * ```
+ *
+ * Properties which panic stop the test without shrinking, so they should
+ * return a [`Verdict`] instead of asserting.
+ */
+pub struct Property<G> {
+    generator: G,
+    cases: usize,
+    seed: u64,
+}
+
+impl<G: Generator> Property<G> {
+    pub fn create(generator: G) -> Self {
+        let seed = std::env::var(SEED_VAR)
+            .ok()
+            .and_then(|seed| seed.parse().ok())
+            .unwrap_or(0);
+        Self {
+            generator,
+            cases: DEFAULT_CASES,
+            seed,
+        }
+    }
+
+    pub fn cases(self, cases: usize) -> Self {
+        Self { cases, ..self }
+    }
+
+    pub fn seed(self, seed: u64) -> Self {
+        Self { seed, ..self }
+    }
+
+    /**
+     * Panics with the minimal counterexample when the property fails.
+     */
+    pub fn check<V: Verdict>(&self, property: impl Fn(&G::Value) -> V) {
+        if let Some(counterexample) = self.find_counterexample(property) {
+            panic!("{counterexample}");
+        }
+    }
+
+    pub fn find_counterexample<V: Verdict>(
+        &self,
+        property: impl Fn(&G::Value) -> V,
+    ) -> Option<Counterexample<G::Value>> {
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        (0..self.cases).find_map(|case| {
+            let original = self.generator.generate(&mut rng);
+            let message = property(&original).failure()?;
+            let (shrunk, shrinks, message) = self.shrink(original.clone(), message, &property);
+            Some(Counterexample {
+                seed: self.seed,
+                case,
+                original,
+                shrunk,
+                shrinks,
+                message,
+            })
+        })
+    }
+
+    fn shrink<V: Verdict>(
+        &self,
+        mut value: G::Value,
+        mut message: String,
+        property: &impl Fn(&G::Value) -> V,
+    ) -> (G::Value, usize, String) {
+        let mut shrinks = 0;
+        while shrinks < MAX_SHRINKS {
+            let simpler = self
+                .generator
+                .shrink(&value)
+                .into_iter()
+                .find_map(|candidate| Some((property(&candidate).failure()?, candidate)));
+            match simpler {
+                Some((simpler_message, candidate)) => {
+                    value = candidate;
+                    message = simpler_message;
+                    shrinks += 1;
+                }
+                None => break,
+            }
+        }
+        (value, shrinks, message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shrink_int() {
+        assert_eq!(int(-5..=5).shrink(&4), [0, 2, 3]);
+        assert_eq!(int(-5..=5).shrink(&-1), [0]);
+        assert_eq!(int(3..=9).shrink(&3), Vec::<i64>::new());
+        assert_eq!(int(3..=9).shrink(&9), [3, 6, 8]);
+    }
+
+    #[test]
+    fn shrinks_to_minimal_counterexample() {
+        let counterexample = Property::create(numbers(0..=20, 0..=100))
+            .seed(0)
+            .find_counterexample(|list| list.iter().sum::<i64>() < 50)
+            .unwrap();
+        assert_eq!(counterexample.shrunk, [50]);
+
+        let counterexample = Property::create(grid(1..=8, 1..=8, &['.', '#']))
+            .seed(0)
+            .find_counterexample(|grid| grid_text(grid).matches('#').count() < 2)
+            .unwrap();
+        assert_eq!(grid_text(&counterexample.shrunk), "##");
+    }
+
+    #[test]
+    fn graphs() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let generated = graph(2..=6, 0.5).generate(&mut rng);
+        assert!(generated
+            .edges
+            .iter()
+            .all(|&(a, b)| a < b && b < generated.nodes));
+
+        let counterexample = Property::create(graph(0..=8, 0.5))
+            .seed(0)
+            .find_counterexample(|g| g.edges.len() < 2)
+            .unwrap();
+        assert_eq!(counterexample.shrunk.edges.len(), 2);
+        assert_eq!(counterexample.shrunk.nodes, 3);
+    }
+
+    #[test]
+    fn passing_property() {
+        Property::create((int(0..=9), numbers(0..=5, 0..=9)))
+            .check(|(n, list)| equal(list.len() + *n as usize, *n as usize + list.len()));
+    }
+
+    #[test]
+    fn block_inputs() {
+        let machines = blocks((int(1..=9), int(1..=9)), 1..=3, |(x, y)| {
+            format!("X={x}\nY={y}")
+        });
+        let mut rng = StdRng::seed_from_u64(0);
+        let input = machines.generate(&mut rng);
+        assert_eq!(input.text.split("\n\n").count(), input.blocks.len());
+        let shrunk = machines.shrink(&BlockInput {
+            blocks: vec![(1, 2), (3, 4)],
+            text: String::new(),
+        });
+        assert_eq!(shrunk[0].text, "X=3\nY=4");
+        assert!(shrunk
+            .iter()
+            .any(|input| input.text == "X=1\nY=2\n\nX=1\nY=4"));
+
+        let counterexample = Property::create(machines)
+            .seed(0)
+            .find_counterexample(|input| !input.text.contains("X=5"))
+            .unwrap();
+        assert_eq!(counterexample.shrunk.text, "X=5\nY=1");
+    }
+}
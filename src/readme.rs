@@ -0,0 +1,379 @@
+use crate::answers::{Answers, ANSWERS_FILE};
+use std::{fs, path::Path};
+
+pub const README_FILE: &str = "README.md";
+
+const PUZZLE_ANSWER_PREFIX: &str = "Your puzzle answer was ";
+
+/**
+ * A fenced code block in a puzzle statement, usually an example input.
+ * `line` is the 1-based line of the opening fence.
+ */
+#[derive(Debug, PartialEq)]
+pub struct CodeBlock {
+    pub part: usize,
+    pub line: usize,
+    pub text: String,
+}
+
+/**
+ * The pieces of a day's `README.md` which the tests depend on: the code
+ * blocks, the text highlighted with inline code, and the accepted answers
+ * recorded after each part was solved.
+ *
+ * Everything before the `## Part 2` (or `## Part Two`) heading is part 1.
+ */
+#[derive(Debug, Default, PartialEq)]
+pub struct Readme {
+    pub blocks: Vec<CodeBlock>,
+    pub highlights: Vec<(usize, String)>,
+    pub puzzle_answers: Vec<(usize, String)>,
+    prose: String,
+}
+
+impl Readme {
+    pub fn parse(text: &str) -> Self {
+        let mut readme = Self::default();
+        let mut part = 1;
+        let mut block: Option<CodeBlock> = None;
+        for (index, line) in text.lines().enumerate() {
+            if line.starts_with("```") {
+                match block.take() {
+                    Some(finished) => readme.blocks.push(finished),
+                    None => {
+                        block = Some(CodeBlock {
+                            part,
+                            line: index + 1,
+                            text: String::new(),
+                        })
+                    }
+                }
+                continue;
+            }
+            if let Some(block) = block.as_mut() {
+                block.text.push_str(line);
+                block.text.push('\n');
+                continue;
+            }
+            if line.starts_with("## Part 2") || line.starts_with("## Part Two") {
+                part = 2;
+            }
+            if let Some(answer) = line.strip_prefix(PUZZLE_ANSWER_PREFIX) {
+                let answer = answer.trim_end_matches('.').trim_matches('`');
+                readme.puzzle_answers.push((part, answer.to_owned()));
+                continue;
+            }
+            readme.highlights.extend(
+                line.split('`')
+                    .skip(1)
+                    .step_by(2)
+                    .filter(|code| !code.is_empty())
+                    .map(|code| (part, code.to_owned())),
+            );
+            readme.prose.push_str(line);
+            readme.prose.push('\n');
+        }
+        readme
+    }
+
+    pub fn read(day_dir: &Path) -> Result<Self, String> {
+        let path = day_dir.join(README_FILE);
+        fs::read_to_string(&path)
+            .map(|text| Self::parse(&text))
+            .map_err(|e| format!("{}: {e}", path.display()))
+    }
+
+    /**
+     * The code block with the same contents as `example`, ignoring trailing
+     * whitespace.
+     */
+    pub fn find_block(&self, example: &str) -> Option<&CodeBlock> {
+        let example = normalize(example);
+        self.blocks.iter().find(|b| normalize(&b.text) == example)
+    }
+
+    /**
+     * Whether the puzzle text mentions `answer`, either highlighted or as a
+     * whole word of the prose.
+     */
+    pub fn mentions(&self, answer: &str) -> bool {
+        self.highlights.iter().any(|(_, code)| code == answer)
+            || self.prose.match_indices(answer).any(|(start, _)| {
+                let is_word = |c: Option<char>| c.is_some_and(|c| c.is_alphanumeric() || c == ',');
+                !is_word(self.prose[..start].chars().next_back())
+                    && !is_word(self.prose[start + answer.len()..].chars().next())
+            })
+    }
+
+    pub fn puzzle_answer(&self, part: usize) -> Option<&str> {
+        self.puzzle_answers
+            .iter()
+            .find(|(p, _)| *p == part)
+            .map(|(_, answer)| answer.as_str())
+    }
+}
+
+fn normalize(text: &str) -> String {
+    text.trim_end()
+        .lines()
+        .map(str::trim_end)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/**
+ * The expected answers of the `verify!` calls and `aoc_examples!` tables in a
+ * day's source, along with the part number they're for. Expectations are
+ * string or integer literals, or coordinates, and any other kind of answer
+ * such as a picture is skipped.
+ */
+pub fn test_expectations(source: &str) -> Vec<(usize, String)> {
+    source
+        .lines()
+        .flat_map(|line| {
+            let line = line.trim();
+            if let Some(args) = line.strip_prefix("verify!(") {
+                let args = args.strip_suffix(");").unwrap_or_default();
+                let expected = arguments(args).last().copied().unwrap_or_default();
+                vec![(args, expected)]
+            } else if let Some(parts) = examples_row(line) {
+                let parts = parts
+                    .trim_end_matches(',')
+                    .strip_suffix('}')
+                    .unwrap_or_default();
+                arguments(parts)
+                    .into_iter()
+                    .filter_map(|part| part.split_once(':'))
+                    .map(|(part, expected)| (part, expected.trim()))
+                    .collect()
+            } else {
+                Vec::new()
+            }
+        })
+        .filter_map(|(part, expected)| {
+            let part = if part.contains("part1") {
+                1
+            } else if part.contains("part2") {
+                2
+            } else {
+                return None;
+            };
+            Some((part, expectation(expected)?))
+        })
+        .collect()
+}
+
+/**
+ * The parts of a row of an `aoc_examples!` table, such as
+ * `example: "example.txt" => { part1: "4" },`, which may name a solution
+ * before them, as in `=> GardenGroups { part1: 4 }`.
+ */
+fn examples_row(line: &str) -> Option<&str> {
+    let (_, parts) = line.split_once("=> ")?;
+    parts
+        .trim_start_matches(|c: char| c.is_ascii_alphanumeric() || c == '_')
+        .trim_start()
+        .strip_prefix('{')
+}
+
+/**
+ * A macro's comma separated arguments, skipping commas nested in brackets or
+ * strings.
+ */
+fn arguments(args: &str) -> Vec<&str> {
+    let mut depth = 0;
+    let mut in_string = false;
+    let mut start = 0;
+    let mut arguments = Vec::new();
+    for (index, c) in args.char_indices() {
+        match c {
+            '"' => in_string = !in_string,
+            '(' | '[' if !in_string => depth += 1,
+            ')' | ']' if !in_string => depth -= 1,
+            ',' if !in_string && depth == 0 => {
+                arguments.push(args[start..index].trim());
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    arguments.push(args[start..].trim());
+    arguments.retain(|argument| !argument.is_empty());
+    arguments
+}
+
+/**
+ * The answer an expected value in a test is written as.
+ */
+fn expectation(expected: &str) -> Option<String> {
+    if let Some(text) = expected.strip_prefix('"') {
+        return Some(text.strip_suffix('"')?.to_owned());
+    }
+    if let Some(coordinates) = expected
+        .strip_prefix("Answer::Coordinates(")
+        .or_else(|| expected.strip_prefix("Answer::coordinates("))
+    {
+        let (x, y) = coordinates.strip_suffix(')')?.split_once(',')?;
+        return Some(format!("{},{}", x.trim(), y.trim()));
+    }
+    // An integer literal, which may have a type suffix such as `u64`.
+    let suffix = expected.trim_start_matches(|c: char| c.is_ascii_digit() || c == '_');
+    let digits = &expected[..expected.len() - suffix.len()];
+    let is_suffix = suffix.is_empty() || suffix.starts_with(['i', 'u']);
+    (!digits.is_empty() && is_suffix).then(|| digits.replace('_', ""))
+}
+
+/**
+ * Compares a day's example files, test expectations and accepted
+ * answers with its `README.md`, returning a description of each mismatch.
+ *
+ * Expectations which aren't mentioned in the puzzle text are only allowed
+ * for inputs which aren't examples from the puzzle, so they're reported as
+ * mismatches too, leaving it to the caller to decide.
+ */
+pub fn validate_day(day_dir: &Path) -> Result<Vec<String>, String> {
+    let readme = Readme::read(day_dir)?;
+    let read = |name: &str| {
+        let path = day_dir.join(name);
+        fs::read_to_string(&path).map_err(|e| format!("{}: {e}", path.display()))
+    };
+    let mut problems = Vec::new();
+    let mut examples = fs::read_dir(day_dir)
+        .map_err(|e| format!("{}: {e}", day_dir.display()))?
+        .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+        .filter(|name| name.starts_with("example") && name.ends_with(".txt"))
+        .collect::<Vec<_>>();
+    examples.sort();
+    for name in examples {
+        if readme.find_block(&read(&name)?).is_none() {
+            problems.push(format!("{name} isn't a code block in {README_FILE}"));
+        }
+    }
+    for (part, expected) in test_expectations(&read("main.rs")?) {
+        if !readme.mentions(&expected) {
+            problems.push(format!(
+                "part{part} expects {expected} which isn't in {README_FILE}"
+            ));
+        }
+    }
+    let answers = Answers::parse(&read(ANSWERS_FILE)?)?;
+    for (part, answer) in readme.puzzle_answers.iter() {
+        let accepted = answers.get(None, &format!("part{part}"));
+        if accepted != Some(answer.as_str()) {
+            problems.push(format!(
+                "part{part} answer {answer} in {README_FILE} but {} in {ANSWERS_FILE}",
+                accepted.unwrap_or("nothing")
+            ));
+        }
+    }
+    Ok(problems)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const README: &str = "\
+# Day 1
+
+For example:
+
+```
+3   4
+4   3
+```
+
+So, the total distance is `11`. Output `4,6,3` then.
+
+Your puzzle answer was `1722302`.
+
+## Part Two
+
+```
+1
+```
+
+This time the score is `31`.
+";
+
+    #[test]
+    fn parse() {
+        let readme = Readme::parse(README);
+        assert_eq!(
+            readme.blocks,
+            [
+                CodeBlock {
+                    part: 1,
+                    line: 5,
+                    text: "3   4\n4   3\n".to_owned()
+                },
+                CodeBlock {
+                    part: 2,
+                    line: 16,
+                    text: "1\n".to_owned()
+                },
+            ]
+        );
+        assert_eq!(
+            readme.highlights,
+            [
+                (1, "11".to_owned()),
+                (1, "4,6,3".to_owned()),
+                (2, "31".to_owned())
+            ]
+        );
+        assert_eq!(readme.puzzle_answer(1), Some("1722302"));
+        assert_eq!(readme.puzzle_answer(2), None);
+    }
+
+    #[test]
+    fn find_block() {
+        let readme = Readme::parse(README);
+        assert_eq!(
+            readme.find_block("3   4 \n4   3\n\n").map(|b| b.line),
+            Some(5)
+        );
+        assert_eq!(readme.find_block("3   4\n"), None);
+    }
+
+    #[test]
+    fn mentions() {
+        let readme = Readme::parse(README);
+        assert!(readme.mentions("11"));
+        assert!(readme.mentions("4,6,3"));
+        assert!(!readme.mentions("3"));
+        assert!(!readme.mentions("6,3"));
+    }
+
+    #[test]
+    fn expectations() {
+        let source = "
+        verify!(part1, input, \"11\");
+        verify!(Solution::part2, input, \"a,b\");
+        assert_eq!(part1(input), \"x\");
+        verify!(part1, input, params, 1_000u64);
+        verify!([part2, part2_fast], input, Answer::Coordinates(6, 1));
+        verify!(tree, input, params, Answer::picture([\"#.\", \".#\"]));
+        aoc_examples! {
+            example: \"example.txt\" => { part1: \"4\", part2: 9 },
+            small: text \"1,2\" with params() => { part2: \"a,b\" },
+            parsed: \"example.txt\" => MySolution { part1: 7 },
+        }
+        Some(x) => { x },
+        None => fallback(part1),
+";
+        assert_eq!(
+            test_expectations(source),
+            [
+                (1, "11".to_owned()),
+                (2, "a,b".to_owned()),
+                (1, "1000".to_owned()),
+                (2, "6,1".to_owned()),
+                (1, "4".to_owned()),
+                (2, "9".to_owned()),
+                (2, "a,b".to_owned()),
+                (1, "7".to_owned())
+            ]
+        );
+    }
+}
@@ -0,0 +1,1091 @@
+use crate::{
+    answers::{Answers, Check},
+    bench::{bench, timed, BenchStats, DEFAULT_BENCH_RUNS},
+    day::Entry,
+    error::AocError,
+    format::{json_record, tsv_record, Format, TSV_HEADER},
+    params::parse_assignment,
+    solution::{Parser, SolutionPart, Solver},
+    trace::{self, Level},
+    Answer, Day, Named, Operation, Params,
+};
+use std::{
+    borrow::Cow,
+    collections::HashSet,
+    env, fs,
+    io::Read,
+    panic::{catch_unwind, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    process::{self, ExitCode},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc, Once,
+    },
+    thread,
+    time::Duration,
+};
+
+/**
+ * Command line options shared by every day binary and the `aoc` binary.
+ *
+ * Any argument which isn't an option is the name of a part to run.
+ * When no parts are named, all of them run.
+ */
+pub struct Options {
+    pub operations: HashSet<String>,
+    pub input_path: Option<String>,
+    pub bench_runs: Option<usize>,
+    pub check: bool,
+    pub format: Format,
+    pub jobs: usize,
+    pub trace: Option<Level>,
+    pub trace_path: Option<String>,
+    pub input_dir: Option<String>,
+    pub profile: Option<String>,
+    pub params: Vec<(String, String)>,
+    pub list: bool,
+    pub help: bool,
+}
+
+const STDIN_PATH: &str = "-";
+
+/**
+ * Used for the input directory when `--input-dir` isn't given.
+ */
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/**
+ * Describes the options of [`Options::parse`], for `--help`.
+ */
+pub const OPTIONS_HELP: &str = "options:
+  --input PATH        run against PATH instead of the embedded inputs, or stdin with `-`
+  --input-dir DIR     run against DIR/<profile>/dayNN.txt for each profile (default $AOC_INPUT_DIR)
+  --profile NAME      only run the input labelled NAME
+  --param NAME=VALUE  override a parameter of every part which declares it
+  --check             compare answers with answers.toml and report PASS, FAIL or UNKNOWN
+  --bench [RUNS]      time each part over RUNS runs (default 10)
+  --jobs [N]          run parts on N worker threads (default one per core)
+  --format FORMAT     print results as text, json or tsv (default text)
+  --trace [LEVEL]     trace each part's steps to stderr, or only which input it starts with `part`
+  --trace-file PATH   write the trace to PATH instead of stderr
+  --list              list the parts which can be named, with their variants and parameters
+  --help              show this help";
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            operations: HashSet::new(),
+            input_path: None,
+            bench_runs: None,
+            check: false,
+            format: Format::default(),
+            jobs: 1,
+            trace: None,
+            trace_path: None,
+            input_dir: None,
+            profile: None,
+            params: Vec::new(),
+            list: false,
+            help: false,
+        }
+    }
+}
+
+impl Options {
+    /**
+     * Parses the command line, taking the input directory from
+     * [`INPUT_DIR_VAR`] unless `--input-dir` is given. Fails on an unknown
+     * option or an option without its value, but part and parameter names
+     * can only be checked against the days with [`Options::check_names`].
+     */
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut options = Self {
+            input_dir: env::var(INPUT_DIR_VAR).ok().filter(|dir| !dir.is_empty()),
+            ..Self::default()
+        };
+        let mut args = args.into_iter().peekable();
+        while let Some(arg) = args.next() {
+            let mut value = |what: &str| args.next().ok_or(format!("{arg} requires {what}"));
+            match arg.as_str() {
+                "--input" => options.input_path = Some(value("a path")?),
+                "--input-dir" => options.input_dir = Some(value("a path")?),
+                "--profile" => options.profile = Some(value("a name")?),
+                "--param" => options
+                    .params
+                    .push(parse_assignment(&value("name=value")?)?),
+                "--check" => options.check = true,
+                "--format" => options.format = value("text, json or tsv")?.parse()?,
+                "--trace-file" => {
+                    options.trace_path = Some(value("a path")?);
+                    options.trace.get_or_insert(Level::Step);
+                }
+                "--bench" => {
+                    let runs = args
+                        .next_if(|n| n.parse::<usize>().is_ok())
+                        .map_or(DEFAULT_BENCH_RUNS, |n| n.parse().unwrap());
+                    options.bench_runs = Some(runs.max(1));
+                }
+                "--jobs" => {
+                    let jobs = args
+                        .next_if(|n| n.parse::<usize>().is_ok())
+                        .map_or_else(default_jobs, |n| n.parse().unwrap());
+                    options.jobs = jobs.max(1);
+                }
+                "--trace" => {
+                    let level = args
+                        .next_if(|l| l.parse::<Level>().is_ok())
+                        .map_or(Level::Step, |l| l.parse().unwrap());
+                    options.trace = Some(level);
+                }
+                "--list" => options.list = true,
+                "--help" | "-h" => options.help = true,
+                STDIN_PATH => options.input_path = Some(arg),
+                _ if arg.starts_with('-') => return Err(format!("unknown option {arg}")),
+                _ => {
+                    options.operations.insert(arg);
+                }
+            }
+        }
+        Ok(options)
+    }
+
+    /**
+     * Checks that every part named on the command line is a part or variant
+     * of at least one of `days`, and that every `--param` is declared by one
+     * of their parts, so that a typo isn't silently ignored.
+     */
+    pub fn check_names<'a>(&self, days: impl IntoIterator<Item = &'a Day>) -> Result<(), String> {
+        let days = days.into_iter().collect::<Vec<_>>();
+        let names =
+            days.iter()
+                .flat_map(|day| day.part_names())
+                .fold(Vec::new(), |mut names, name| {
+                    if !names.contains(&name) {
+                        names.push(name);
+                    }
+                    names
+                });
+        let mut unknown = self
+            .operations
+            .iter()
+            .filter(|operation| !names.contains(&operation.as_str()))
+            .collect::<Vec<_>>();
+        unknown.sort();
+        if let Some(unknown) = unknown.first() {
+            return Err(format!(
+                "unknown part {unknown}, expected one of {}",
+                names.join(", ")
+            ));
+        }
+        for (name, _) in self.params.iter() {
+            let declared =
+                days.iter()
+                    .flat_map(|day| day.entries.iter())
+                    .any(|entry| match entry {
+                        Entry::Part { params, .. } | Entry::Solution { params, .. } => {
+                            params.declares(name)
+                        }
+                    });
+            if !declared {
+                return Err(format!("no part takes the parameter {name}"));
+            }
+        }
+        Ok(())
+    }
+
+    fn is_enabled(&self, name: &str) -> bool {
+        self.operations.is_empty() || self.operations.contains(name)
+    }
+}
+
+/**
+ * Used for `--jobs` without a number.
+ */
+fn default_jobs() -> usize {
+    thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+/**
+ * The outcome of running one part, or one variant of a part, against one input.
+ * `answer` and `elapsed` are `None` when the part was disabled.
+ * In bench mode `elapsed` is the median time of all the runs.
+ *
+ * `parse` is the time a [`crate::Solution`] spent parsing the input. It's
+ * recorded against the first part which needed the parsed input, and the
+ * following parts of the same solution reuse it for free.
+ *
+ * `error` is set instead of `answer` when the part failed, and in check mode
+ * `check` compares `answer` to the accepted answer for the input. For a
+ * variant, `check` instead compares `answer` to the answer of `variant_of`.
+ */
+pub struct Record {
+    pub day: &'static str,
+    pub part: &'static str,
+    pub input: String,
+    pub answer: Option<Answer>,
+    pub parse: Option<Duration>,
+    pub elapsed: Option<Duration>,
+    pub bench: Option<BenchStats>,
+    pub check: Option<Check>,
+    pub error: Option<AocError>,
+    pub variant_of: Option<&'static str>,
+}
+
+impl Record {
+    fn disabled(day: &Day, part: &'static str, input: &str) -> Self {
+        Self {
+            day: day.name,
+            part,
+            input: input.to_owned(),
+            answer: None,
+            parse: None,
+            elapsed: None,
+            bench: None,
+            check: None,
+            error: None,
+            variant_of: None,
+        }
+    }
+
+    fn set_result(&mut self, result: Result<Answer, AocError>) {
+        match result {
+            Ok(answer) => self.answer = Some(answer),
+            Err(error) => self.error = Some(error.in_part(self.day, self.part)),
+        }
+    }
+
+    pub fn status(&self) -> &'static str {
+        match (&self.answer, &self.error) {
+            (Some(_), _) => "ok",
+            (None, Some(_)) => "error",
+            (None, None) => "disabled",
+        }
+    }
+
+    fn is_failure(&self) -> bool {
+        self.error.is_some() || matches!(self.check, Some(Check::Fail { .. }))
+    }
+}
+
+/**
+ * One input to run a day against, along with its accepted answers in check mode.
+ * `violation` is set when the input is beyond one of the day's constraints, and
+ * is every part's error instead of running it.
+ */
+struct Input {
+    label: String,
+    text: Cow<'static, str>,
+    answers: Answers,
+    table: Option<String>,
+    violation: Option<AocError>,
+}
+
+/**
+ * Running one entry of a day against one input.
+ */
+struct Task<'a> {
+    day: &'a Day,
+    input: &'a Input,
+    entry: &'a Entry,
+    show_label: bool,
+}
+
+/**
+ * Some days recurse deeply, so workers get as much stack as the main thread.
+ */
+const WORKER_STACK_SIZE: usize = 8 * 1024 * 1024;
+
+impl Input {
+    fn check(&self, part: &str, answer: &Answer) -> Check {
+        let expected = self.answers.get(self.table.as_deref(), part);
+        Check::compare(&answer.to_string(), expected)
+    }
+}
+
+pub struct Runner {
+    options: Options,
+    tsv_header: Once,
+}
+
+impl Runner {
+    /**
+     * Creates a runner with the options on the command line, exiting with
+     * an error when they can't be parsed.
+     */
+    pub fn create() -> Self {
+        match Options::parse(env::args().skip(1)) {
+            Ok(options) => Self::with_options(options),
+            Err(e) => {
+                eprintln!("{e}\nrun with --help to see the options");
+                process::exit(2)
+            }
+        }
+    }
+
+    /**
+     * Tracing is process wide, so creating a runner with `--trace` starts it.
+     */
+    pub fn with_options(options: Options) -> Self {
+        if let Some(level) = options.trace {
+            let sink = trace::open(options.trace_path.as_deref()).unwrap_or_else(|e| panic!("{e}"));
+            trace::enable(level, sink);
+        }
+        Self {
+            options,
+            tsv_header: Once::new(),
+        }
+    }
+
+    pub fn options(&self) -> &Options {
+        &self.options
+    }
+
+    /**
+     * Returns the labelled inputs to run `day` against.
+     *
+     * An input given on the command line replaces all of the others and is
+     * labelled with its path. Otherwise, when there's an input directory,
+     * each profile directory in it with a file for the day is an input
+     * labelled with the profile, as in `inputs/<profile>/day06.txt`. Days
+     * without any such files fall back to their embedded inputs.
+     * `--profile` picks out one input by its label, ignoring case, leaving
+     * none for days without it.
+     *
+     * In check mode each input also gets its accepted answers. Those for the
+     * embedded inputs come from the day's `answers.toml`. Those for an input
+     * file come from the `answers.toml` in the same directory, using the top
+     * level keys for `input.txt` and the table named after the file's stem
+     * for any other file, such as `[day06]`.
+     */
+    fn inputs(&self, day: &Day) -> Result<Vec<Input>, String> {
+        if let Some(path) = &self.options.input_path {
+            return Ok(vec![self.file_input(path.clone(), path)?]);
+        }
+        let profiles = match self.options.input_dir.as_deref() {
+            Some(dir) => profile_inputs(Path::new(dir), day.name)?,
+            None => Vec::new(),
+        };
+        let inputs = if profiles.is_empty() {
+            self.embedded_inputs(day)
+        } else {
+            profiles
+                .into_iter()
+                .map(|(label, path)| self.file_input(label, &path.to_string_lossy()))
+                .collect::<Result<_, _>>()?
+        };
+        Ok(match &self.options.profile {
+            Some(profile) => inputs
+                .into_iter()
+                .filter(|i| i.label.eq_ignore_ascii_case(profile))
+                .collect(),
+            None => inputs,
+        })
+    }
+
+    fn file_input(&self, label: String, path: &str) -> Result<Input, String> {
+        let (answers, table) = if self.options.check && path != STDIN_PATH {
+            let stem = Path::new(path).file_stem().and_then(|s| s.to_str());
+            (
+                Answers::read_beside(path)?,
+                stem.filter(|&s| s != "input").map(str::to_owned),
+            )
+        } else {
+            (Answers::default(), None)
+        };
+        Ok(Input {
+            label,
+            text: Cow::Owned(read_input(path)?),
+            answers,
+            table,
+            violation: None,
+        })
+    }
+
+    fn embedded_inputs(&self, day: &Day) -> Vec<Input> {
+        let answers = match day.answers {
+            Some(text) if self.options.check => {
+                Answers::parse(text).unwrap_or_else(|e| panic!("{} answers.toml {e}", day.name))
+            }
+            _ => Answers::default(),
+        };
+        day.inputs
+            .iter()
+            .enumerate()
+            .map(|(index, &(label, text))| Input {
+                label: label.to_owned(),
+                text: Cow::Borrowed(text),
+                answers: answers.clone(),
+                table: (index > 0).then(|| label.to_owned()),
+                violation: None,
+            })
+            .collect()
+    }
+
+    /**
+     * Runs every enabled part of `day` against each of its inputs, printing
+     * the answers as they're computed.
+     */
+    pub fn run_day(&self, day: &Day) -> Result<Vec<Record>, String> {
+        self.run_days([day])
+    }
+
+    /**
+     * Runs every enabled part of each day against each of its inputs.
+     *
+     * Each part is a task, except that the parts of a solution make up one
+     * task since they share a parse. Tasks run on a pool of `--jobs` worker
+     * threads, and each record is printed as soon as the tasks before it have
+     * finished, so the output is in the same order however many jobs there are.
+     *
+     * Fails before running anything when an input can't be read, or when
+     * none of the days has an input for `--profile`.
+     */
+    pub fn run_days<'a>(
+        &self,
+        days: impl IntoIterator<Item = &'a Day>,
+    ) -> Result<Vec<Record>, String> {
+        let days = days
+            .into_iter()
+            .map(|day| {
+                let mut inputs = self.inputs(day)?;
+                for input in inputs.iter_mut() {
+                    input.violation = day.validate(&input.text).err();
+                }
+                Ok((day, inputs))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        if let Some(profile) = &self.options.profile {
+            if days.iter().all(|(_, inputs)| inputs.is_empty()) {
+                return Err(format!("no input for profile {profile}"));
+            }
+        }
+        let tasks = days
+            .iter()
+            .flat_map(|(day, inputs)| {
+                let show_label = inputs.len() > 1;
+                inputs.iter().flat_map(move |input| {
+                    day.entries.iter().map(move |entry| Task {
+                        day,
+                        input,
+                        entry,
+                        show_label,
+                    })
+                })
+            })
+            .collect::<Vec<_>>();
+        let next_task = AtomicUsize::new(0);
+        let (sender, receiver) = mpsc::channel();
+        thread::scope(|scope| {
+            for _ in 0..self.options.jobs.min(tasks.len()) {
+                let (tasks, next_task, sender) = (&tasks, &next_task, sender.clone());
+                let worker = move || loop {
+                    let index = next_task.fetch_add(1, Ordering::Relaxed);
+                    let Some(task) = tasks.get(index) else {
+                        break;
+                    };
+                    if sender.send((index, self.run_task(task))).is_err() {
+                        break;
+                    }
+                };
+                thread::Builder::new()
+                    .stack_size(WORKER_STACK_SIZE)
+                    .spawn_scoped(scope, worker)
+                    .expect("failed to spawn worker thread");
+            }
+            drop(sender);
+            let mut finished = tasks.iter().map(|_| None).collect::<Vec<_>>();
+            let mut next_to_print = 0;
+            let mut records = Vec::new();
+            for (index, task_records) in receiver {
+                finished[index] = Some(task_records);
+                while let Some(task_records) =
+                    finished.get_mut(next_to_print).and_then(Option::take)
+                {
+                    for record in task_records {
+                        self.print_record(&record, tasks[next_to_print].show_label);
+                        records.push(record);
+                    }
+                    next_to_print += 1;
+                }
+            }
+            Ok(records)
+        })
+    }
+
+    fn run_task(&self, task: &Task) -> Vec<Record> {
+        let Task {
+            day, input, entry, ..
+        } = *task;
+        let mut records = match entry {
+            Entry::Part {
+                op,
+                params,
+                variants,
+            } => self.run_variants(day, op, variants, params, input),
+            Entry::Solution { parse, params } => self.run_solution(day, *parse, params, input),
+        };
+        if self.options.check {
+            for record in records.iter_mut().filter(|r| r.variant_of.is_none()) {
+                record.check = record.answer.as_ref().map(|a| input.check(record.part, a));
+            }
+        }
+        records
+    }
+
+    /**
+     * Runs `day` from its own binary, returning a failure exit code when any
+     * part fails or fails its check.
+     */
+    pub fn run(&self, day: &Day) -> ExitCode {
+        if self.options.help {
+            println!("usage: {} [parts...] [options]\n\n{OPTIONS_HELP}", day.name);
+            return ExitCode::SUCCESS;
+        }
+        if self.options.list {
+            list_parts(day).iter().for_each(|part| println!("{part}"));
+            return ExitCode::SUCCESS;
+        }
+        match self
+            .options
+            .check_names([day])
+            .and_then(|()| self.run_day(day))
+        {
+            Ok(records) => exit_code(&records),
+            Err(e) => {
+                eprintln!("{e}");
+                ExitCode::from(2)
+            }
+        }
+    }
+
+    fn print_record(&self, record: &Record, show_label: bool) {
+        match self.options.format {
+            Format::Text => print_text_record(record, show_label),
+            Format::Json => println!("{}", json_record(record)),
+            Format::Tsv => {
+                self.tsv_header.call_once(|| println!("{TSV_HEADER}"));
+                println!("{}", tsv_record(record));
+            }
+        }
+    }
+
+    /**
+     * Times `f`, running it repeatedly in bench mode.
+     */
+    fn measure<T>(&self, f: impl FnMut() -> T) -> (T, Duration, Option<BenchStats>) {
+        match self.options.bench_runs {
+            Some(runs) => {
+                let (result, stats) = bench(runs, f);
+                (result, stats.median, Some(stats))
+            }
+            None => {
+                let (result, elapsed) = timed(f);
+                (result, elapsed, None)
+            }
+        }
+    }
+
+    /**
+     * Runs a part followed by its variants, which also run when only the
+     * part is named. Each variant is checked against the part's answer.
+     */
+    fn run_variants(
+        &self,
+        day: &Day,
+        op: &Named<Operation>,
+        variants: &[Named<Operation>],
+        defaults: &Params,
+        input: &Input,
+    ) -> Vec<Record> {
+        let part_enabled = self.options.is_enabled(op.name);
+        let main = self.run_part(day, op, defaults, input, part_enabled);
+        let variants = variants
+            .iter()
+            .map(|variant| {
+                let enabled = part_enabled || self.options.is_enabled(variant.name);
+                let mut record = self.run_part(day, variant, defaults, input, enabled);
+                record.variant_of = Some(op.name);
+                if let (Some(answer), Some(expected)) = (&record.answer, &main.answer) {
+                    record.check = Some(match answer == expected {
+                        true => Check::Pass,
+                        false => Check::Fail {
+                            expected: expected.to_string(),
+                        },
+                    });
+                }
+                record
+            })
+            .collect::<Vec<_>>();
+        let mut records = vec![main];
+        records.extend(variants);
+        records
+    }
+
+    fn run_part(
+        &self,
+        day: &Day,
+        op: &Named<Operation>,
+        defaults: &Params,
+        input: &Input,
+        enabled: bool,
+    ) -> Record {
+        let mut record = Record::disabled(day, op.name, &input.label);
+        if let (true, Some(violation)) = (enabled, &input.violation) {
+            record.set_result(Err(violation.clone()));
+        } else if enabled {
+            let (result, elapsed, stats) = trace::scoped(day.name, op.name, || {
+                let params = defaults.overridden(&self.options.params);
+                crate::trace!(
+                    Part,
+                    "input {}{}",
+                    input.label,
+                    match params.is_empty() {
+                        true => String::new(),
+                        false => format!(" with {params}"),
+                    }
+                );
+                self.measure(|| catch(|| (op.wrapped)(&input.text, &params)))
+            });
+            record.set_result(result);
+            record.elapsed = Some(elapsed);
+            record.bench = stats;
+        }
+        record
+    }
+
+    fn run_solution(
+        &self,
+        day: &Day,
+        parse: Parser,
+        defaults: &Params,
+        input: &Input,
+    ) -> Vec<Record> {
+        let params = defaults.overridden(&self.options.params);
+        let mut solver: Option<Result<Solver, AocError>> = None;
+        SolutionPart::ALL
+            .into_iter()
+            .map(|solution_part| {
+                let part = solution_part.name();
+                let mut record = Record::disabled(day, part, &input.label);
+                if self.options.is_enabled(part) {
+                    trace::scoped(day.name, part, || {
+                        crate::trace!(Part, "input {}", input.label);
+                        let solver = solver.get_or_insert_with(|| {
+                            if let Some(violation) = &input.violation {
+                                return Err(violation.clone());
+                            }
+                            let (solver, parse_time, _) =
+                                self.measure(|| catch(|| parse(&input.text, &params)));
+                            record.parse = Some(parse_time);
+                            solver
+                        });
+                        match solver {
+                            Ok(solver) => {
+                                let (result, elapsed, stats) =
+                                    self.measure(|| catch(|| Ok(solver(solution_part))));
+                                record.set_result(result);
+                                record.elapsed = Some(elapsed);
+                                record.bench = stats;
+                            }
+                            Err(error) => record.set_result(Err(error.clone())),
+                        }
+                    });
+                }
+                record
+            })
+            .collect()
+    }
+}
+
+/**
+ * Runs `f`, turning a panic into an error so that the other parts still run.
+ */
+fn catch<T>(f: impl FnOnce() -> Result<T, AocError>) -> Result<T, AocError> {
+    catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|payload| Err(AocError::from_panic(payload)))
+}
+
+fn print_text_record(record: &Record, show_label: bool) {
+    let label = if show_label {
+        format!(" [{}]", record.input)
+    } else {
+        String::new()
+    };
+    let parse = match (&record.bench, record.parse) {
+        (Some(_), Some(parse)) => format!("parse median {parse:.2?}; "),
+        (None, Some(parse)) => format!("parse {parse:.2?} + "),
+        (_, None) => String::new(),
+    };
+    let timing = match (&record.bench, record.elapsed) {
+        (Some(stats), _) => format!(" ({parse}{stats})"),
+        (None, Some(elapsed)) => format!(" ({parse}{elapsed:.2?})"),
+        (None, None) => String::new(),
+    };
+    let check = record
+        .check
+        .as_ref()
+        .map(|c| format!(" {c}"))
+        .unwrap_or_default();
+    let answer = match (&record.answer, &record.error) {
+        (Some(answer), _) => answer.to_string(),
+        (None, Some(error)) => format!("error: {error}"),
+        (None, None) => "(DISABLED)".to_owned(),
+    };
+    println!(
+        "{} {}{label}{timing}:{check}\n{answer}",
+        record.day, record.part
+    );
+}
+
+/**
+ * Describes each part of `day` which can be named on the command line, for
+ * `--list`, along with what it's a variant of and its parameters.
+ */
+pub fn list_parts(day: &Day) -> Vec<String> {
+    let describe = |name: &str, variant_of: Option<&str>, params: &Params| {
+        let mut line = format!("{} {name}", day.name);
+        if let Some(part) = variant_of {
+            line += &format!(" (variant of {part})");
+        }
+        if !params.is_empty() {
+            line += &format!(" {params}");
+        }
+        line
+    };
+    day.entries
+        .iter()
+        .flat_map(|entry| match entry {
+            Entry::Part {
+                op,
+                params,
+                variants,
+            } => std::iter::once(describe(op.name, None, params))
+                .chain(
+                    variants
+                        .iter()
+                        .map(|variant| describe(variant.name, Some(op.name), params)),
+                )
+                .collect::<Vec<_>>(),
+            Entry::Solution { params, .. } => SolutionPart::ALL
+                .iter()
+                .map(|part| describe(part.name(), None, params))
+                .collect(),
+        })
+        .collect()
+}
+
+/**
+ * Failure when any part failed or failed its check, otherwise success.
+ */
+pub fn exit_code(records: &[Record]) -> ExitCode {
+    if records.iter().any(Record::is_failure) {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/**
+ * Prints a table with one row per record, followed by the total time taken.
+ * A check column is included when the records were checked.
+ */
+pub fn print_summary(records: &[Record]) {
+    let checked = records.iter().any(|r| r.check.is_some());
+    let rows = records
+        .iter()
+        .map(|r| {
+            let mut row = vec![
+                r.day.to_owned(),
+                r.part.to_owned(),
+                r.input.clone(),
+                match (&r.answer, &r.error) {
+                    (Some(answer), _) => answer.summary(),
+                    (None, Some(_)) => "(ERROR)".to_owned(),
+                    (None, None) => "(DISABLED)".to_owned(),
+                },
+                r.parse.map(|e| format!("{e:.2?}")).unwrap_or_default(),
+                r.elapsed.map(|e| format!("{e:.2?}")).unwrap_or_default(),
+            ];
+            if checked {
+                row.push(r.check.as_ref().map(Check::to_string).unwrap_or_default());
+            }
+            row
+        })
+        .collect::<Vec<_>>();
+    let mut header = ["day", "part", "input", "answer", "parse", "time"]
+        .map(str::to_owned)
+        .to_vec();
+    if checked {
+        header.push("check".to_owned());
+    }
+    let mut widths = header.iter().map(String::len).collect::<Vec<_>>();
+    for row in rows.iter() {
+        for (width, cell) in widths.iter_mut().zip(row.iter()) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    let print_row = |row: &[String]| {
+        let cells = row
+            .iter()
+            .zip(widths.iter())
+            .map(|(cell, &width)| format!("{cell:width$}"))
+            .collect::<Vec<_>>();
+        println!("{}", cells.join("  ").trim_end());
+    };
+    print_row(&header);
+    for row in rows.iter() {
+        print_row(row);
+    }
+    let total: Duration = records
+        .iter()
+        .flat_map(|r| r.parse.into_iter().chain(r.elapsed))
+        .sum();
+    println!("total time {total:.2?}");
+    if checked {
+        let count = |f: fn(&Check) -> bool| {
+            records
+                .iter()
+                .filter(|r| r.check.as_ref().is_some_and(f))
+                .count()
+        };
+        println!(
+            "{} passed, {} failed, {} unknown",
+            count(|c| *c == Check::Pass),
+            count(|c| matches!(c, Check::Fail { .. })),
+            count(|c| *c == Check::Unknown),
+        );
+    }
+}
+
+/**
+ * The input files for `day_name` in the profile directories of `input_dir`,
+ * along with the profile names, in order of name.
+ */
+fn profile_inputs(input_dir: &Path, day_name: &str) -> Result<Vec<(String, PathBuf)>, String> {
+    let entries = fs::read_dir(input_dir).map_err(|e| {
+        format!(
+            "failed to read input directory {}: {e}",
+            input_dir.display()
+        )
+    })?;
+    let mut inputs = entries
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            let path = entry.path().join(format!("{day_name}.txt"));
+            let profile = entry.file_name().into_string().ok()?;
+            path.is_file().then_some((profile, path))
+        })
+        .collect::<Vec<_>>();
+    inputs.sort();
+    Ok(inputs)
+}
+
+/**
+ * Reads a file, or stdin when `path` is `-`.
+ */
+pub fn read_input(path: &str) -> Result<String, String> {
+    if path == STDIN_PATH {
+        let mut input = String::new();
+        std::io::stdin()
+            .read_to_string(&mut input)
+            .map_err(|e| format!("failed to read input from stdin: {e}"))?;
+        Ok(input)
+    } else {
+        std::fs::read_to_string(path).map_err(|e| format!("failed to read input {path}: {e}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{constraint::Constraint, named, Named};
+    use std::str::Lines;
+
+    fn slow(input: Lines) -> String {
+        thread::sleep(Duration::from_millis(20));
+        input.count().to_string()
+    }
+
+    fn fast(input: Lines) -> String {
+        input.count().to_string()
+    }
+
+    fn parse(args: &str) -> Result<Options, String> {
+        Options::parse(args.split_whitespace().map(str::to_owned))
+    }
+
+    #[test]
+    fn command_line() {
+        let options = parse("part2 --input - --format json --bench 3 --param n=5").unwrap();
+        assert_eq!(options.operations, HashSet::from(["part2".to_owned()]));
+        assert_eq!(options.input_path.as_deref(), Some(STDIN_PATH));
+        assert_eq!(options.format, Format::Json);
+        assert_eq!(options.bench_runs, Some(3));
+        assert_eq!(options.params, [("n".to_owned(), "5".to_owned())]);
+        assert!(parse("--list").unwrap().list);
+        assert_eq!(
+            parse("--bogus").err().as_deref(),
+            Some("unknown option --bogus")
+        );
+        assert_eq!(
+            parse("--profile").err().as_deref(),
+            Some("--profile requires a name")
+        );
+        assert!(parse("--format xml").is_err());
+    }
+
+    #[test]
+    fn part_names() {
+        let day = Day::create("day01")
+            .part_with_params(named!(slow), &[("n", "1")])
+            .variant(named!(fast));
+        assert_eq!(parse("fast slow").unwrap().check_names([&day]), Ok(()));
+        assert_eq!(
+            parse("slow part3").unwrap().check_names([&day]),
+            Err("unknown part part3, expected one of slow, fast".to_owned())
+        );
+        assert_eq!(parse("--param n=2").unwrap().check_names([&day]), Ok(()));
+        assert_eq!(
+            parse("--param m=2").unwrap().check_names([&day]),
+            Err("no part takes the parameter m".to_owned())
+        );
+        assert_eq!(
+            list_parts(&day),
+            ["day01 slow n=1", "day01 fast (variant of slow) n=1"]
+        );
+    }
+
+    #[test]
+    fn jobs_keep_order() {
+        let day = Day::create("day01")
+            .labelled_input("one", "a")
+            .labelled_input("two", "a\nb")
+            .part(named!(slow))
+            .part(named!(fast));
+        let runner = Runner::with_options(Options {
+            jobs: 4,
+            ..Default::default()
+        });
+        let records = runner.run_days([&day, &day]).unwrap();
+        let summary = records
+            .iter()
+            .map(|r| format!("{} {} {}", r.part, r.input, r.answer.as_ref().unwrap()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            ["slow one 1", "fast one 1", "slow two 2", "fast two 2"].repeat(2)
+        );
+    }
+
+    #[test]
+    fn profiles() {
+        let input_dir = std::env::temp_dir().join(format!("aoc-profiles-{}", std::process::id()));
+        for (profile, text) in [("jared", "a\nb\nc"), ("fred", "a")] {
+            fs::create_dir_all(input_dir.join(profile)).unwrap();
+            fs::write(input_dir.join(profile).join("day01.txt"), text).unwrap();
+        }
+        fs::create_dir_all(input_dir.join("empty")).unwrap();
+        let day = Day::create("day01")
+            .labelled_input("embedded", "a\nb")
+            .part(named!(fast));
+        let run = |profile: Option<&str>| {
+            let runner = Runner::with_options(Options {
+                input_dir: Some(input_dir.to_string_lossy().into_owned()),
+                profile: profile.map(str::to_owned),
+                ..Default::default()
+            });
+            runner.run_day(&day).map(|records| {
+                records
+                    .iter()
+                    .map(|r| format!("{} {}", r.input, r.answer.as_ref().unwrap()))
+                    .collect::<Vec<_>>()
+            })
+        };
+        assert_eq!(run(None).unwrap(), ["fred 1", "jared 3"]);
+        assert_eq!(run(Some("Jared")).unwrap(), ["jared 3"]);
+        assert_eq!(
+            run(Some("mary")),
+            Err("no input for profile mary".to_owned())
+        );
+
+        let other_day = Day::create("day02")
+            .labelled_input("embedded", "a\nb")
+            .part(named!(fast));
+        let runner = Runner::with_options(Options {
+            input_dir: Some(input_dir.to_string_lossy().into_owned()),
+            ..Default::default()
+        });
+        assert_eq!(runner.run_day(&other_day).unwrap()[0].input, "embedded");
+
+        fs::remove_dir_all(&input_dir).unwrap();
+        assert!(runner
+            .run_day(&day)
+            .is_err_and(|e| e.starts_with("failed to read input directory")));
+        let runner = Runner::with_options(Options {
+            input_path: Some(input_dir.join("missing.txt").to_string_lossy().into_owned()),
+            ..Default::default()
+        });
+        assert!(runner
+            .run_day(&day)
+            .is_err_and(|e| e.starts_with("failed to read input")));
+    }
+
+    #[test]
+    fn constraints() {
+        let day = Day::create("day01")
+            .labelled_input("small", "1\n2")
+            .labelled_input("large", "1\n300")
+            .constraint(Constraint::numbers_at_most(255, "u8"))
+            .part(named!(fast));
+        let records = Runner::with_options(Options::default())
+            .run_day(&day)
+            .unwrap();
+        assert_eq!(records[0].answer, Some(Answer::text("2")));
+        assert_eq!(
+            records[1]
+                .error
+                .as_ref()
+                .map(AocError::to_string)
+                .as_deref(),
+            Some("day01 fast: line 2, column 1: number 300 exceeds u8 (at most 255)")
+        );
+        assert_eq!(exit_code(&records), ExitCode::FAILURE);
+    }
+
+    fn fast_wrong(input: Lines) -> String {
+        (input.count() + 1).to_string()
+    }
+
+    #[test]
+    fn variants_agree() {
+        let day = Day::create("day01")
+            .labelled_input("one", "a\nb")
+            .part(named!(slow))
+            .variant(named!(fast))
+            .variant(named!(fast_wrong));
+        let records = Runner::with_options(Options::default())
+            .run_day(&day)
+            .unwrap();
+        let summary = records
+            .iter()
+            .map(|r| {
+                let check = r.check.as_ref().map(Check::to_string).unwrap_or_default();
+                format!("{} {} {check}", r.part, r.answer.as_ref().unwrap())
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            ["slow 2 ", "fast 2 PASS", "fast_wrong 3 FAIL (expected 2)"]
+        );
+        assert_eq!(exit_code(&records), ExitCode::FAILURE);
+
+        let only_variant = Runner::with_options(Options {
+            operations: ["fast".to_owned()].into(),
+            ..Default::default()
+        })
+        .run_day(&day)
+        .unwrap();
+        let statuses = only_variant.iter().map(Record::status).collect::<Vec<_>>();
+        assert_eq!(statuses, ["disabled", "ok", "disabled"]);
+        assert_eq!(only_variant[1].check, None);
+    }
+}
@@ -0,0 +1,275 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+pub const TEMPLATE_DIR: &str = "template";
+
+/**
+ * A new day to create from the template, with whatever is already known
+ * about its puzzle.
+ *
+ * `answers` are the expected answers for the example of each part, which
+ * replace the template's placeholder `0` in its `aoc_examples!` table. Ones
+ * which aren't integers are expected as text.
+ */
+#[derive(Default)]
+pub struct NewDay {
+    pub number: u32,
+    pub title: Option<String>,
+    pub example: Option<String>,
+    pub answers: [Option<String>; 2],
+}
+
+impl NewDay {
+    pub fn create(number: u32) -> Self {
+        Self {
+            number,
+            ..Default::default()
+        }
+    }
+
+    pub fn name(&self) -> String {
+        format!("day{:02}", self.number)
+    }
+
+    /**
+     * The template's `main.rs` with the example answers filled in.
+     */
+    pub fn main_rs(&self, template: &str) -> String {
+        self.answers
+            .iter()
+            .enumerate()
+            .fold(
+                template.to_owned(),
+                |main_rs, (index, answer)| match answer {
+                    Some(answer) => {
+                        let part = format!("part{}", index + 1);
+                        let expected = match answer.parse::<i128>() {
+                            // integer literals are `i32` unless they say otherwise
+                            Ok(n) if i32::try_from(n).is_ok() => n.to_string(),
+                            Ok(n) => format!("{n}i128"),
+                            Err(_) => format!("{answer:?}"),
+                        };
+                        main_rs.replace(&format!("{part}: 0"), &format!("{part}: {expected}"))
+                    }
+                    None => main_rs,
+                },
+            )
+    }
+
+    pub fn readme(&self) -> String {
+        let title = match &self.title {
+            Some(title) => format!("Day {}: {title}", self.number),
+            None => format!("Day {}", self.number),
+        };
+        format!(
+            "# {title}\n\nhttps://adventofcode.com/2024/day/{}\n\n## Part 2\n",
+            self.number
+        )
+    }
+
+    /**
+     * Creates the day's directory in `bin_dir` from the template directory
+     * next to it, returning the new directory. Refuses to touch a day which
+     * already exists.
+     */
+    pub fn write(&self, bin_dir: &Path) -> Result<PathBuf, String> {
+        let template_dir = bin_dir.join(TEMPLATE_DIR);
+        let day_dir = bin_dir.join(self.name());
+        if day_dir.exists() {
+            return Err(format!("{} already exists", day_dir.display()));
+        }
+        let io_error = |path: &Path, e: std::io::Error| format!("{}: {e}", path.display());
+        fs::create_dir_all(&day_dir).map_err(|e| io_error(&day_dir, e))?;
+        let entries = fs::read_dir(&template_dir).map_err(|e| io_error(&template_dir, e))?;
+        for entry in entries {
+            let entry = entry.map_err(|e| io_error(&template_dir, e))?;
+            let target = day_dir.join(entry.file_name());
+            fs::copy(entry.path(), &target).map_err(|e| io_error(&target, e))?;
+        }
+        let main_rs = day_dir.join("main.rs");
+        let template = fs::read_to_string(&main_rs).map_err(|e| io_error(&main_rs, e))?;
+        let files = [
+            (main_rs, Some(self.main_rs(&template))),
+            (day_dir.join("example.txt"), self.example.clone()),
+            (day_dir.join("README.md"), Some(self.readme())),
+        ];
+        for (path, contents) in files {
+            if let Some(contents) = contents {
+                fs::write(&path, contents).map_err(|e| io_error(&path, e))?;
+            }
+        }
+        Ok(day_dir)
+    }
+}
+
+/**
+ * Adds a day to the source of the `aoc` binary, both as a module and in
+ * `days()`, keeping the days in order. Returns `None` when it's already there,
+ * and an error when the source isn't laid out as expected.
+ */
+pub fn register(aoc_main: &str, name: &str) -> Result<Option<String>, String> {
+    let mut lines = aoc_main.lines().map(str::to_owned).collect::<Vec<_>>();
+    let module = format!("mod {name};");
+    if lines.contains(&module) {
+        return Ok(None);
+    }
+    let is_earlier_day = |line: &str, prefix: &str, suffix: &str| {
+        line.strip_prefix(prefix)
+            .and_then(|l| l.strip_suffix(suffix))
+            .is_some_and(|day| day.starts_with("day") && day < name)
+    };
+
+    let entry_line = lines
+        .iter()
+        .rposition(|l| is_earlier_day(l.trim(), "", "::day(),"))
+        .or_else(|| lines.iter().position(|l| l.trim() == "vec!["))
+        .ok_or_else(|| format!("didn't find the `vec![` of days to add {name} to"))?;
+    let indent = "        ";
+    lines.insert(entry_line + 1, format!("{indent}{name}::day(),"));
+
+    let module_line = lines
+        .iter()
+        .rposition(|l| is_earlier_day(l, "mod ", ";"))
+        .map(|index| index + 1)
+        .or_else(|| {
+            let first_path = lines
+                .iter()
+                .position(|l| l.starts_with("#[path = \"../day"))?;
+            Some(first_path.saturating_sub(1))
+        })
+        .or_else(|| lines.iter().position(|l| l.starts_with("fn days()")))
+        .ok_or_else(|| format!("didn't find where to declare mod {name}"))?;
+    let declaration = [
+        "#[allow(dead_code)]".to_owned(),
+        format!("#[path = \"../{name}/main.rs\"]"),
+        module,
+    ];
+    lines.splice(module_line..module_line, declaration);
+    Ok(Some(lines.join("\n") + "\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEMPLATE: &str = "example: \"example.txt\" => { part1: 0, part2: 0 },\n";
+
+    #[test]
+    fn main_rs() {
+        let mut day = NewDay::create(3);
+        assert_eq!(day.main_rs(TEMPLATE), TEMPLATE);
+        day.answers = [Some("161".to_owned()), Some("a,b".to_owned())];
+        assert_eq!(
+            day.main_rs(TEMPLATE),
+            "example: \"example.txt\" => { part1: 161, part2: \"a,b\" },\n"
+        );
+        day.answers = [Some("-3".to_owned()), Some("875318608908".to_owned())];
+        assert_eq!(
+            day.main_rs(TEMPLATE),
+            "example: \"example.txt\" => { part1: -3, part2: 875318608908i128 },\n"
+        );
+    }
+
+    #[test]
+    fn readme() {
+        let day = NewDay {
+            title: Some("Mull It Over".to_owned()),
+            ..NewDay::create(3)
+        };
+        assert_eq!(day.name(), "day03");
+        assert!(day.readme().starts_with("# Day 3: Mull It Over\n"));
+    }
+
+    #[test]
+    fn write() {
+        let bin_dir = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        let template_dir = bin_dir.join(TEMPLATE_DIR);
+        fs::create_dir_all(&template_dir).unwrap();
+        fs::write(template_dir.join("main.rs"), TEMPLATE).unwrap();
+        fs::write(template_dir.join("input.txt"), "").unwrap();
+        fs::write(template_dir.join("example.txt"), "").unwrap();
+        let day = NewDay {
+            example: Some("1 2\n".to_owned()),
+            answers: [Some("3".to_owned()), None],
+            ..NewDay::create(7)
+        };
+
+        let day_dir = day.write(&bin_dir).unwrap();
+        let read = |file: &str| fs::read_to_string(day_dir.join(file)).unwrap();
+        assert_eq!(read("example.txt"), "1 2\n");
+        assert_eq!(read("input.txt"), "");
+        assert!(read("main.rs").contains("part1: 3, part2: 0"));
+        assert!(read("README.md").starts_with("# Day 7\n"));
+        assert!(day.write(&bin_dir).is_err());
+
+        fs::remove_dir_all(&bin_dir).unwrap();
+    }
+
+    #[test]
+    fn register_in_order() {
+        let aoc_main = "\
+#[allow(dead_code)]
+#[path = \"../day01/main.rs\"]
+mod day01;
+#[allow(dead_code)]
+#[path = \"../day03/main.rs\"]
+mod day03;
+
+fn days() -> Vec<Day> {
+    vec![
+        day01::day(),
+        day03::day(),
+    ]
+}
+";
+        let registered = register(aoc_main, "day02").unwrap().unwrap();
+        assert_eq!(
+            registered,
+            "\
+#[allow(dead_code)]
+#[path = \"../day01/main.rs\"]
+mod day01;
+#[allow(dead_code)]
+#[path = \"../day02/main.rs\"]
+mod day02;
+#[allow(dead_code)]
+#[path = \"../day03/main.rs\"]
+mod day03;
+
+fn days() -> Vec<Day> {
+    vec![
+        day01::day(),
+        day02::day(),
+        day03::day(),
+    ]
+}
+"
+        );
+        assert_eq!(register(&registered, "day02"), Ok(None));
+        assert!(register(aoc_main, "day00")
+            .unwrap()
+            .unwrap()
+            .starts_with("#[allow(dead_code)]\n#[path = \"../day00/main.rs\"]\nmod day00;\n#[allow(dead_code)]\n#[path = \"../day01"));
+        let first = register("fn days() -> Vec<Day> {\n    vec![\n    ]\n}\n", "day01")
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            first,
+            "#[allow(dead_code)]\n#[path = \"../day01/main.rs\"]\nmod day01;\nfn days() -> Vec<Day> {\n    vec![\n        day01::day(),\n    ]\n}\n"
+        );
+    }
+
+    #[test]
+    fn register_without_anchors() {
+        assert_eq!(
+            register("fn days() -> Vec<Day> {\n    Vec::new()\n}\n", "day01"),
+            Err("didn't find the `vec![` of days to add day01 to".to_owned())
+        );
+        assert_eq!(
+            register("fn all() -> Vec<Day> {\n    vec![\n    ]\n}\n", "day01"),
+            Err("didn't find where to declare mod day01".to_owned())
+        );
+    }
+}
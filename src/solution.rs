@@ -0,0 +1,104 @@
+use crate::{Answer, AocError, Params};
+use std::str::Lines;
+
+/**
+ * A day whose parts share one parse of the input.
+ *
+ * `parse` runs once per input and both parts receive the parsed value, so
+ * the runner can time parsing separately from solving each part. Parsing
+ * reports bad input as an error, and reads any parameters the day declares
+ * with [`crate::Day::solution_with_params`].
+ */
+pub trait Solution: 'static {
+    type Parsed: 'static;
+
+    fn parse(input: Lines, params: &Params) -> Result<Self::Parsed, AocError>;
+    fn part1(parsed: &Self::Parsed) -> impl Into<Answer>;
+    fn part2(parsed: &Self::Parsed) -> impl Into<Answer>;
+}
+
+/**
+ * One of the two parts of a [`Solution`].
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SolutionPart {
+    Part1,
+    Part2,
+}
+
+impl SolutionPart {
+    pub const ALL: [Self; 2] = [Self::Part1, Self::Part2];
+
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Part1 => "part1",
+            Self::Part2 => "part2",
+        }
+    }
+}
+
+/**
+ * Solves a part using an already parsed input.
+ */
+pub type Solver = Box<dyn Fn(SolutionPart) -> Answer>;
+
+/**
+ * The type erased `parse` of a [`Solution`], so days can be stored together.
+ */
+pub type Parser = fn(&str, &Params) -> Result<Solver, AocError>;
+
+pub fn parser<S: Solution>() -> Parser {
+    |input, params| {
+        let parsed = S::parse(input.lines(), params)?;
+        Ok(Box::new(move |part| match part {
+            SolutionPart::Part1 => S::part1(&parsed).into(),
+            SolutionPart::Part2 => S::part2(&parsed).into(),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Sum;
+
+    impl Solution for Sum {
+        type Parsed = Vec<u32>;
+
+        fn parse(input: Lines, params: &Params) -> Result<Self::Parsed, AocError> {
+            let scale = params.get::<u32>("scale")?;
+            input
+                .map(|line| {
+                    line.parse::<u32>()
+                        .map(|n| n * scale)
+                        .map_err(|e| AocError::new(e.to_string()))
+                })
+                .collect()
+        }
+
+        fn part1(parsed: &Self::Parsed) -> impl Into<Answer> {
+            parsed.iter().sum::<u32>()
+        }
+
+        fn part2(parsed: &Self::Parsed) -> impl Into<Answer> {
+            parsed.iter().product::<u32>()
+        }
+    }
+
+    #[test]
+    fn parse_once_solve_both() {
+        let params = Params::create(&[("scale", "1")]);
+        let solver = parser::<Sum>()("2\n3\n4", &params).unwrap();
+        assert_eq!(solver(SolutionPart::Part1), Answer::Integer(9));
+        assert_eq!(solver(SolutionPart::Part2), Answer::Integer(24));
+        let solver = parser::<Sum>()("2\n3", &params.with("scale", 2)).unwrap();
+        assert_eq!(solver(SolutionPart::Part1), Answer::Integer(10));
+    }
+
+    #[test]
+    fn parse_error() {
+        let params = Params::create(&[("scale", "1")]);
+        assert!(parser::<Sum>()("2\nx", &params).is_err());
+    }
+}
@@ -0,0 +1,186 @@
+use std::{
+    cell::Cell,
+    fmt,
+    fs::File,
+    io::{self, LineWriter, Write},
+    str::FromStr,
+    sync::{
+        atomic::{AtomicU8, Ordering},
+        Mutex,
+    },
+};
+
+/**
+ * How much detail to trace. Each level includes the ones before it.
+ *
+ * `Part` traces the runner starting each part, and `Step` adds the events
+ * which parts write with [`crate::trace!`] as they work through the input.
+ */
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub enum Level {
+    Part = 1,
+    Step = 2,
+}
+
+impl Level {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Part => "part",
+            Self::Step => "step",
+        }
+    }
+}
+
+impl FromStr for Level {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "part" => Ok(Self::Part),
+            "step" => Ok(Self::Step),
+            _ => Err(format!("unknown trace level {s}, expected part or step")),
+        }
+    }
+}
+
+/**
+ * The most detailed level being traced, or 0 when tracing is off, so that
+ * checking whether to trace is a single atomic load.
+ */
+static LEVEL: AtomicU8 = AtomicU8::new(0);
+
+static SINK: Mutex<Option<Box<dyn Write + Send>>> = Mutex::new(None);
+
+thread_local! {
+    static SCOPE: Cell<Option<(&'static str, &'static str)>> = const { Cell::new(None) };
+}
+
+/**
+ * Opens the file to write trace events to, or stderr when there's no path.
+ */
+pub fn open(path: Option<&str>) -> Result<Box<dyn Write + Send>, String> {
+    match path {
+        Some(path) => File::create(path)
+            .map(|file| Box::new(LineWriter::new(file)) as Box<dyn Write + Send>)
+            .map_err(|e| format!("failed to create trace file {path}: {e}")),
+        None => Ok(Box::new(io::stderr())),
+    }
+}
+
+/**
+ * Starts writing events at `level` and below to `sink`, for the whole process.
+ */
+pub fn enable(level: Level, sink: Box<dyn Write + Send>) {
+    *SINK.lock().unwrap() = Some(sink);
+    LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+pub fn disable() {
+    LEVEL.store(0, Ordering::Relaxed);
+    SINK.lock().unwrap().take();
+}
+
+#[inline]
+pub fn enabled(level: Level) -> bool {
+    LEVEL.load(Ordering::Relaxed) >= level as u8
+}
+
+/**
+ * Runs `f` with the events written on this thread attributed to `part` of `day`.
+ */
+pub fn scoped<T>(day: &'static str, part: &'static str, f: impl FnOnce() -> T) -> T {
+    let outer = SCOPE.replace(Some((day, part)));
+    let result = f();
+    SCOPE.set(outer);
+    result
+}
+
+/**
+ * Writes an event, prefixed by the day and part it came from and its level.
+ * Events spanning several lines, such as maps, start on the line after the
+ * prefix. Use [`crate::trace!`] rather than calling this directly, so that
+ * the event is only formatted when it's being traced.
+ */
+pub fn event(level: Level, args: fmt::Arguments) {
+    let message = args.to_string();
+    let mut line = match SCOPE.get() {
+        Some((day, part)) => format!("{day} {part} {}:", level.name()),
+        None => format!("{}:", level.name()),
+    };
+    line.push(if message.contains('\n') { '\n' } else { ' ' });
+    line.push_str(&message);
+    line.push('\n');
+    if let Some(sink) = SINK.lock().unwrap().as_mut() {
+        // Tracing is best effort, a sink which can't be written to is ignored.
+        let _ = sink.write_all(line.as_bytes());
+    }
+}
+
+/**
+ * Traces an event at the given [`Level`], formatting its arguments like
+ * `format!`. When the level isn't being traced the arguments aren't evaluated.
+ *
+ * ```
+ * advent_of_code::trace!(Step, "moved to {:?}", (1, 2));
+ * ```
+ */
+#[macro_export]
+macro_rules! trace {
+    ( $level:ident, $($arg:tt)+ ) => {
+        if $crate::trace::enabled($crate::trace::Level::$level) {
+            $crate::trace::event($crate::trace::Level::$level, format_args!($($arg)+));
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn parse_level() {
+        assert_eq!("part".parse(), Ok(Level::Part));
+        assert_eq!("step".parse(), Ok(Level::Step));
+        assert!("all".parse::<Level>().is_err());
+    }
+
+    #[test]
+    fn events() {
+        let mut evaluated = false;
+        crate::trace!(Step, "{}", {
+            evaluated = true;
+            1
+        });
+        assert!(!evaluated);
+
+        let buffer = Buffer::default();
+        enable(Level::Part, Box::new(buffer.clone()));
+        scoped("day99", "part1", || {
+            crate::trace!(Part, "x={}", 1);
+            crate::trace!(Step, "y={}", 2);
+            crate::trace!(Part, "map\n{}", "#.\n.#");
+        });
+        disable();
+        crate::trace!(Part, "z={}", 3);
+
+        let text = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        assert!(text.contains("day99 part1 part: x=1\n"));
+        assert!(text.contains("day99 part1 part:\nmap\n#.\n.#\n"));
+        assert!(!text.contains("y=2"));
+        assert!(!text.contains("z=3"));
+    }
+}
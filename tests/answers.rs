@@ -0,0 +1,28 @@
+use std::process::Command;
+
+/**
+ * Runs every day with `--check`, failing if any answer differs from its
+ * `answers.toml`. Some days are slow without optimizations, so this is
+ * ignored by default and best run with
+ * `cargo test --release --test answers -- --ignored`.
+ */
+#[test]
+#[ignore = "slow in debug builds"]
+fn check_all_days() {
+    let output = Command::new(env!("CARGO_BIN_EXE_aoc"))
+        .args(["all", "--check"])
+        .current_dir(env!("CARGO_TARGET_TMPDIR"))
+        .output()
+        .expect("failed to run aoc");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let failures = stdout
+        .lines()
+        .filter(|line| line.contains("FAIL"))
+        .collect::<Vec<_>>();
+    assert!(
+        output.status.success(),
+        "failed checks:\n{}\n{}",
+        failures.join("\n"),
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
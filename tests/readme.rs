@@ -0,0 +1,54 @@
+use advent_of_code::{
+    answers::ANSWERS_FILE,
+    readme::{validate_day, README_FILE},
+};
+use std::{fs, path::Path};
+
+/**
+ * Differences from the puzzle text which are expected, because the test uses
+ * an input of its own, or the puzzle text for part 2 hasn't been copied into
+ * the README yet.
+ */
+const KNOWN_DIFFERENCES: &[(&str, &str)] = &[
+    (
+        "day13",
+        "part2 expects 875318608908 which isn't in README.md",
+    ),
+    ("day15", "part2 expects 618 which isn't in README.md"),
+    ("day16", "part2 expects 45 which isn't in README.md"),
+    ("day16", "part2 expects 64 which isn't in README.md"),
+    ("day25", "part2 expects 0 which isn't in README.md"),
+];
+
+/**
+ * Checks the examples and tests of every day with a README against the
+ * puzzle text, so any drift between them is noticed.
+ */
+#[test]
+fn examples_match_readme() {
+    let bin_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/bin");
+    let mut days = fs::read_dir(&bin_dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.join(README_FILE).exists() && path.join(ANSWERS_FILE).exists())
+        .collect::<Vec<_>>();
+    days.sort();
+    let mut found = Vec::new();
+    for day_dir in days {
+        let name = day_dir.file_name().unwrap().to_str().unwrap().to_owned();
+        for problem in validate_day(&day_dir).unwrap() {
+            found.push((name.clone(), problem));
+        }
+    }
+    let unexpected = found
+        .iter()
+        .filter(|(name, problem)| !KNOWN_DIFFERENCES.contains(&(name.as_str(), problem.as_str())))
+        .map(|(name, problem)| format!("{name}: {problem}"))
+        .collect::<Vec<_>>();
+    assert!(unexpected.is_empty(), "{}", unexpected.join("\n"));
+    let fixed = KNOWN_DIFFERENCES
+        .iter()
+        .filter(|&&(name, problem)| !found.iter().any(|(n, p)| n == name && p == problem))
+        .collect::<Vec<_>>();
+    assert!(fixed.is_empty(), "no longer different: {fixed:?}");
+}